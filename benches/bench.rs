@@ -11,16 +11,37 @@ pub fn bench_from_str(c: &mut Criterion) {
     });
 }
 
+pub fn bench_parse(c: &mut Criterion) {
+    let raw_purl = "pkg:type/name/space/name@version?k1=v1&k2=v2#sub/path";
+    c.bench_function("parse_borrowed", |c| {
+        c.iter(|| {
+            let _ = PackageUrl::parse(raw_purl).unwrap();
+        })
+    });
+}
+
 pub fn bench_from_url(c: &mut Criterion) {
     let raw_purl = "pkg:type/name/space/name@version?k1=v1&k2=v2#sub/path";
-    c.bench_function("parse", |c| {
+    c.bench_function("url_parse", |c| {
         c.iter(|| {
             let _ = url::Url::parse(raw_purl).unwrap();
         })
     });
 }
 
+pub fn bench_write_canonical(c: &mut Criterion) {
+    let raw_purl = "pkg:type/name/space/name@version?k1=v1&k2=v2#sub/path";
+    let purl = PackageUrl::parse(raw_purl).unwrap();
+    let mut buffer = String::with_capacity(purl.canonical_len());
+    c.bench_function("write_canonical", |c| {
+        c.iter(|| {
+            buffer.clear();
+            purl.write_canonical(&mut buffer).unwrap();
+        })
+    });
+}
+
 criterion_group! {
-  benches, bench_from_str, bench_from_url
+  benches, bench_from_str, bench_parse, bench_from_url, bench_write_canonical
 }
 criterion_main!(benches);
@@ -0,0 +1,149 @@
+//! Embed the type definitions from `types/*-definition.json` in the crate.
+//!
+//! Each definition is turned into a `TypeDefinition` literal, and all of them
+//! are written sorted by type to `$OUT_DIR/definitions.rs`, which is included
+//! by `src/registry.rs`. The position of each type in that list is written as
+//! a `match` on `ty` to `$OUT_DIR/index.rs`, to look definitions up without
+//! comparing the type against several of them.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+fn main() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("types");
+    println!("cargo:rerun-if-changed={}", dir.display());
+
+    let mut definitions = Vec::new();
+    for entry in fs::read_dir(&dir).expect("failed to read the types directory") {
+        let path = entry.expect("failed to read the types directory").path();
+        let file_name = path.file_name().unwrap().to_string_lossy().into_owned();
+        let Some(ty) = file_name.strip_suffix("-definition.json") else {
+            continue;
+        };
+        println!("cargo:rerun-if-changed={}", path.display());
+        let text = fs::read_to_string(&path).expect("failed to read a type definition");
+        let json: Value = serde_json::from_str(&text)
+            .unwrap_or_else(|e| panic!("invalid JSON in {}: {}", file_name, e));
+        if json["type"].as_str() != Some(ty) {
+            panic!("{} does not define the {:?} type", file_name, ty);
+        }
+        definitions.push((ty.to_string(), definition(ty, &json)));
+    }
+    definitions.sort();
+
+    let mut code = String::from("&[\n");
+    let mut index = String::from("match ty {\n");
+    for (i, (ty, definition)) in definitions.into_iter().enumerate() {
+        code.push_str(&definition);
+        writeln!(index, "    {:?} => Some({}),", ty, i).unwrap();
+    }
+    code.push_str("]\n");
+    index.push_str("    _ => None,\n}\n");
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let out = Path::new(&out_dir).join("definitions.rs");
+    fs::write(out, code).expect("failed to write the type definitions");
+    let out = Path::new(&out_dir).join("index.rs");
+    fs::write(out, index).expect("failed to write the type definition index");
+}
+
+/// Generate the `TypeDefinition` literal for a type.
+fn definition(ty: &str, json: &Value) -> String {
+    let repository_url = json["repository"]["default_repository_url"].as_str();
+    let mut code = String::new();
+    writeln!(code, "    TypeDefinition {{").unwrap();
+    writeln!(code, "        ty: {:?},", ty).unwrap();
+    writeln!(code, "        type_name: {:?},", string(json, "type_name")).unwrap();
+    writeln!(
+        code,
+        "        description: {:?},",
+        string(json, "description")
+    )
+    .unwrap();
+    writeln!(
+        code,
+        "        default_repository_url: {:?},",
+        repository_url
+    )
+    .unwrap();
+    let namespace = component(ty, &json["namespace_definition"], "optional");
+    writeln!(code, "        namespace: {},", namespace).unwrap();
+    let name = component(ty, &json["name_definition"], "required");
+    writeln!(code, "        name: {},", name).unwrap();
+    let version = component(ty, &json["version_definition"], "optional");
+    writeln!(code, "        version: {},", version).unwrap();
+    writeln!(code, "        qualifiers: &[").unwrap();
+    for qualifier in json["qualifiers_definition"]
+        .as_array()
+        .into_iter()
+        .flatten()
+    {
+        writeln!(
+            code,
+            "            QualifierDefinition {{ key: {:?}, requirement: {}, description: {:?} }},",
+            string(qualifier, "key"),
+            requirement(ty, qualifier, "optional"),
+            string(qualifier, "description"),
+        )
+        .unwrap();
+    }
+    writeln!(code, "        ],").unwrap();
+    writeln!(code, "    }},").unwrap();
+    code
+}
+
+/// Generate the `ComponentDefinition` literal for a component definition.
+fn component(ty: &str, json: &Value, default_requirement: &str) -> String {
+    let case_sensitive = json["case_sensitive"].as_bool().unwrap_or(true);
+    let mut normalization = String::new();
+    for rule in json["normalization_rules"].as_array().into_iter().flatten() {
+        let rule = rule.as_str().expect("normalization rules must be strings");
+        let (from, to) = replacement(rule).unwrap_or_else(|| {
+            panic!(
+                "unknown normalization rule for the {:?} type: {:?}",
+                ty, rule
+            )
+        });
+        write!(
+            normalization,
+            "Normalization::Replace({:?}, {:?}), ",
+            from, to
+        )
+        .unwrap();
+    }
+    format!(
+        "ComponentDefinition {{ requirement: {}, case_sensitive: {}, normalization: &[{}] }}",
+        requirement(ty, json, default_requirement),
+        case_sensitive,
+        normalization.trim_end_matches(", "),
+    )
+}
+
+/// Recognize a "Replace <what> <from> with <what> <to>" normalization rule.
+fn replacement(rule: &str) -> Option<(char, char)> {
+    let rule = rule.strip_prefix("Replace ")?;
+    let (from, to) = rule.split_once(" with ")?;
+    let single = |s: &str| {
+        let mut chars = s.split_whitespace().last()?.chars();
+        chars.next().filter(|_| chars.next().is_none())
+    };
+    Some((single(from)?, single(to)?))
+}
+
+/// Generate the `Requirement` variant of a definition.
+fn requirement(ty: &str, json: &Value, default: &str) -> &'static str {
+    match json["requirement"].as_str().unwrap_or(default) {
+        "required" => "Requirement::Required",
+        "optional" => "Requirement::Optional",
+        "prohibited" => "Requirement::Prohibited",
+        other => panic!("invalid requirement for the {:?} type: {:?}", ty, other),
+    }
+}
+
+/// Get a string field of a definition, or an empty string.
+fn string<'a>(json: &'a Value, key: &str) -> &'a str {
+    json[key].as_str().unwrap_or_default()
+}
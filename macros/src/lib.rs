@@ -0,0 +1,88 @@
+//! Compile-time validated Package URLs for the [`packageurl`] crate.
+//!
+//! The [`purl!`] macro parses a Package URL while compiling, so that a typo
+//! in a hard-coded Package URL is a compile error instead of a panic:
+//! ```rust
+//! use packageurl::PackageUrl;
+//! use packageurl_macros::purl;
+//!
+//! let purl: PackageUrl<'static> = purl!("pkg:cargo/serde@1.0.0");
+//! assert_eq!(purl.name(), "serde");
+//! assert_eq!(purl.version(), Some("1.0.0"));
+//! ```
+//!
+//! [`packageurl`]: https://docs.rs/packageurl/
+
+use packageurl::PackageUrl;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse_macro_input;
+use syn::LitStr;
+
+/// Parse a Package URL at compile time.
+///
+/// The argument must be a string literal, which is parsed with
+/// [`PackageUrl::parse`]. If it is not a valid Package URL, compilation fails
+/// with the error message. Otherwise, the macro expands to a
+/// `PackageUrl<'static>` borrowing the canonical components from string
+/// literals, without parsing or validating anything at runtime.
+///
+/// The expansion refers to the `packageurl` crate, which must be a dependency
+/// of the crate using the macro.
+///
+/// # Example
+/// ```rust
+/// use packageurl_macros::purl;
+///
+/// let purl = purl!("pkg:npm/%40Angular/Animation@12.3.1");
+/// assert_eq!(purl.namespace(), Some("@Angular"));
+/// assert_eq!(purl.name(), "animation");
+/// assert_eq!(purl.to_string(), "pkg:npm/%40Angular/animation@12.3.1");
+/// ```
+///
+/// Invalid Package URLs are rejected:
+/// ```rust,compile_fail
+/// use packageurl_macros::purl;
+///
+/// let purl = purl!("pkg:cargo");
+/// ```
+///
+/// [`PackageUrl::parse`]: https://docs.rs/packageurl/latest/packageurl/struct.PackageUrl.html#method.parse
+#[proc_macro]
+pub fn purl(input: TokenStream) -> TokenStream {
+    let literal = parse_macro_input!(input as LitStr);
+    let value = literal.value();
+    match PackageUrl::parse(&value) {
+        Ok(purl) => expand(&purl).into(),
+        Err(e) => syn::Error::new(literal.span(), e).to_compile_error().into(),
+    }
+}
+
+/// Generate the construction of a parsed Package URL.
+fn expand(purl: &PackageUrl) -> TokenStream2 {
+    let ty = purl.ty();
+    let namespace = option(purl.namespace());
+    let name = purl.name();
+    let version = option(purl.version());
+    let qualifiers = purl.qualifiers().iter().map(|(k, v)| quote!((#k, #v)));
+    let subpath = option(purl.subpath());
+    quote! {
+        ::packageurl::PackageUrl::from_canonical_parts(
+            #ty,
+            #namespace,
+            #name,
+            #version,
+            &[#(#qualifiers),*],
+            #subpath,
+        )
+    }
+}
+
+/// Generate an optional string literal.
+fn option(value: Option<&str>) -> TokenStream2 {
+    match value {
+        Some(v) => quote!(::core::option::Option::Some(#v)),
+        None => quote!(::core::option::Option::None),
+    }
+}
@@ -0,0 +1,36 @@
+use std::str::FromStr;
+
+use packageurl::PackageUrl;
+use packageurl_macros::purl;
+
+#[test]
+fn test_matches_runtime_parsing() {
+    let cases = [
+        (purl!("pkg:cargo/serde@1.0.0"), "pkg:cargo/serde@1.0.0"),
+        (
+            purl!("pkg:PyPI/Django_Package@1.11.1?repository_url=https://example.com&arch=x86"),
+            "pkg:PyPI/Django_Package@1.11.1?repository_url=https://example.com&arch=x86",
+        ),
+        (
+            purl!("pkg:maven/org.apache.commons/io@1.3.4#sub/path/"),
+            "pkg:maven/org.apache.commons/io@1.3.4#sub/path/",
+        ),
+        (
+            purl!("pkg:generic/my%20package?Checksum=sha256:0123"),
+            "pkg:generic/my%20package?Checksum=sha256:0123",
+        ),
+    ];
+    for (purl, input) in cases {
+        let expected = PackageUrl::from_str(input).unwrap();
+        assert_eq!(purl, expected);
+        assert_eq!(purl.to_string(), expected.to_string());
+    }
+}
+
+#[test]
+fn test_static() {
+    fn purl() -> PackageUrl<'static> {
+        purl!("pkg:github/package-url/purl-spec@244fd47e07d1004")
+    }
+    assert_eq!(purl().namespace(), Some("package-url"));
+}
@@ -0,0 +1,271 @@
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use super::errors::Component;
+use super::errors::Error;
+use super::errors::ErrorKind;
+use super::errors::Result;
+use super::parser::Components;
+use super::purl::PackageUrl;
+use super::qualifiers::Qualifiers;
+use super::registry::TypeRegistry;
+use super::validation;
+
+/// A builder for a [`PackageUrl`].
+///
+/// Components are collected without being checked, and are all validated and
+/// normalized at once when calling [`PackageUrlBuilder::build`].
+///
+/// # Example
+/// ```rust
+/// # extern crate packageurl;
+/// let purl = packageurl::PackageUrl::builder("maven", "commons-io")
+///     .namespace("commons-io")
+///     .version("2.11.0")
+///     .qualifier("type", "jar")
+///     .build()
+///     .unwrap();
+/// assert_eq!(purl.to_string(), "pkg:maven/commons-io/commons-io@2.11.0?type=jar");
+/// ```
+#[derive(Debug, Clone)]
+pub struct PackageUrlBuilder<'a> {
+    ty: Cow<'a, str>,
+    namespace: Option<Cow<'a, str>>,
+    name: Cow<'a, str>,
+    version: Option<Cow<'a, str>>,
+    qualifiers: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+    subpath: Option<Cow<'a, str>>,
+}
+
+impl<'a> PackageUrlBuilder<'a> {
+    /// Create a new builder with the provided type and name.
+    pub fn new<T, N>(ty: T, name: N) -> Self
+    where
+        T: Into<Cow<'a, str>>,
+        N: Into<Cow<'a, str>>,
+    {
+        Self {
+            ty: ty.into(),
+            namespace: None,
+            name: name.into(),
+            version: None,
+            qualifiers: Vec::new(),
+            subpath: None,
+        }
+    }
+
+    /// Set the namespace of the package.
+    pub fn namespace<N>(mut self, namespace: N) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+    {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Set the version of the package.
+    pub fn version<V>(mut self, version: V) -> Self
+    where
+        V: Into<Cow<'a, str>>,
+    {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Add a qualifier to the package.
+    ///
+    /// Keys must be unique: giving the same key more than once is an error.
+    /// Values must not be empty, like with [`Qualifiers::insert`].
+    pub fn qualifier<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<Cow<'a, str>>,
+        V: Into<Cow<'a, str>>,
+    {
+        self.qualifiers.push((key.into(), value.into()));
+        self
+    }
+
+    /// Set the subpath of the package.
+    pub fn subpath<S>(mut self, subpath: S) -> Self
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        self.subpath = Some(subpath.into());
+        self
+    }
+
+    /// Validate all components and build the Package URL.
+    ///
+    /// Every invalid component is reported: if there is more than one, an
+    /// [`ErrorKind::Multiple`] error listing all of them is returned. This
+    /// includes the errors found by checking the Package URL against the
+    /// built-in rules of its type, e.g. for a required namespace.
+    pub fn build(self) -> Result<PackageUrl<'a>> {
+        let mut errors = Vec::new();
+
+        if !validation::is_type_valid(&self.ty) {
+            errors.push(
+                Error::new(ErrorKind::InvalidType(self.ty.to_string()))
+                    .in_component(Component::Type),
+            );
+        }
+        if self.name.is_empty() {
+            errors.push(Error::new(ErrorKind::MissingName).in_component(Component::Name));
+        }
+        if let Some(ref ns) = self.namespace {
+            for component in ns.split('/') {
+                if !validation::is_namespace_component_valid(component) {
+                    errors.push(
+                        Error::new(ErrorKind::InvalidNamespaceComponent(component.into()))
+                            .in_component(Component::Namespace),
+                    );
+                }
+            }
+        }
+        if let Some(ref sp) = self.subpath {
+            for segment in sp.split('/') {
+                if !validation::is_subpath_segment_valid(segment) {
+                    errors.push(
+                        Error::new(ErrorKind::InvalidSubpathSegment(segment.into()))
+                            .in_component(Component::Subpath),
+                    );
+                }
+            }
+        }
+        let mut qualifiers = Qualifiers::new();
+        for (k, v) in self.qualifiers {
+            if qualifiers.contains_key(&k) {
+                errors.push(
+                    Error::new(ErrorKind::DuplicateKey(k.into_owned()))
+                        .in_component(Component::Qualifier),
+                );
+            } else if let Err(e) = qualifiers.insert(k, v) {
+                errors.push(e);
+            }
+        }
+
+        // the type rules can only be checked with a type and a name
+        if validation::is_type_valid(&self.ty) && !self.name.is_empty() {
+            let components = Components {
+                ty: self.ty,
+                namespace: self.namespace,
+                name: self.name,
+                version: self.version,
+                qualifiers,
+                subpath: self.subpath,
+            };
+            let registry = TypeRegistry::builtin();
+            let purl = PackageUrl::from_components(components, registry);
+            match registry.validate(&purl) {
+                Ok(()) if errors.is_empty() => return Ok(purl),
+                Ok(()) => {}
+                Err(e) => errors.push(e),
+            }
+        }
+
+        match errors.len() {
+            1 => Err(errors.remove(0)),
+            _ => Err(Error::new(ErrorKind::Multiple(errors))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_build() {
+        let purl = PackageUrlBuilder::new("GitHub", "Package-URL")
+            .namespace("Package-URL")
+            .version("v1.0")
+            .subpath("docs/README.md")
+            .qualifier("Tag", "v1.0")
+            .build()
+            .unwrap();
+        assert_eq!(
+            purl.to_string(),
+            "pkg:github/package-url/package-url@v1.0?tag=v1.0#docs/README.md"
+        );
+    }
+
+    #[test]
+    fn test_build_single_error() {
+        let err = PackageUrlBuilder::new("bad type", "name")
+            .build()
+            .unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::InvalidType("bad type".into()));
+        assert_eq!(err.component(), Some(Component::Type));
+    }
+
+    #[test]
+    fn test_build_type_rules() {
+        let err = PackageUrlBuilder::new("maven", "io").build().unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::MissingNamespace("maven".into()));
+        let err = PackageUrlBuilder::new("oci", "app")
+            .namespace("org")
+            .version("latest")
+            .build()
+            .unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::ProhibitedNamespace("oci".into()));
+        let err = PackageUrlBuilder::new("oci", "app")
+            .version("latest")
+            .build()
+            .unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::InvalidVersion("latest".into()));
+    }
+
+    #[test]
+    fn test_build_empty_qualifier() {
+        let err = PackageUrlBuilder::new("type", "name")
+            .qualifier("arch", "")
+            .build()
+            .unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::EmptyQualifierValue("arch".into()));
+        assert_eq!(err.component(), Some(Component::Qualifier));
+    }
+
+    #[test]
+    fn test_build_all_errors() {
+        let err = PackageUrlBuilder::new("type", "")
+            .namespace("name//space")
+            .subpath("sub/../path")
+            .qualifier("1st", "v1")
+            .qualifier("k2", "v2")
+            .qualifier("K2", "v3")
+            .build()
+            .unwrap_err();
+        let ErrorKind::Multiple(errors) = err.kind() else {
+            panic!("expected multiple errors, got {:?}", err);
+        };
+        let kinds = errors.iter().map(Error::kind).collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            [
+                &ErrorKind::MissingName,
+                &ErrorKind::InvalidNamespaceComponent("".into()),
+                &ErrorKind::InvalidSubpathSegment("..".into()),
+                &ErrorKind::InvalidKey("1st".into()),
+                &ErrorKind::DuplicateKey("K2".into()),
+            ]
+        );
+
+        let err = PackageUrlBuilder::new("maven", "io")
+            .subpath("sub/../path")
+            .build()
+            .unwrap_err();
+        let ErrorKind::Multiple(errors) = err.kind() else {
+            panic!("expected multiple errors, got {:?}", err);
+        };
+        let kinds = errors.iter().map(Error::kind).collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            [
+                &ErrorKind::InvalidSubpathSegment("..".into()),
+                &ErrorKind::MissingNamespace("maven".into()),
+            ]
+        );
+    }
+}
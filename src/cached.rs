@@ -0,0 +1,123 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::hash::Hash;
+use core::hash::Hasher;
+use core::ops::Deref;
+
+use super::purl::PackageUrl;
+
+/// A Package URL along with its canonical form, formatted once.
+///
+/// Formatting, comparing and hashing use the cached string, which makes this
+/// type a good fit for Package URLs that are written or looked up many times.
+/// The Package URL cannot be modified, so that the cache is always up to
+/// date: use [`CachedPackageUrl::into_inner`] to get it back.
+///
+/// # Example
+/// ```rust
+/// # extern crate packageurl;
+/// use std::collections::HashSet;
+/// use packageurl::PackageUrl;
+///
+/// let purl = PackageUrl::parse("pkg:npm/%40angular/core@12.3.1").unwrap();
+/// let cached = purl.into_cached();
+/// assert_eq!(cached.as_str(), "pkg:npm/%40angular/core@12.3.1");
+/// assert_eq!(cached.name(), "core");
+///
+/// let set = HashSet::from([cached]);
+/// assert!(set.contains("pkg:npm/%40angular/core@12.3.1"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CachedPackageUrl<'a> {
+    purl: PackageUrl<'a>,
+    canonical: Box<str>,
+}
+
+impl<'a> CachedPackageUrl<'a> {
+    /// Format the canonical form of a Package URL and keep it.
+    pub fn new(purl: PackageUrl<'a>) -> Self {
+        let mut canonical = String::with_capacity(purl.canonical_len());
+        let _ = purl.write_canonical(&mut canonical);
+        Self {
+            purl,
+            canonical: canonical.into_boxed_str(),
+        }
+    }
+
+    /// Get the canonical form of the Package URL.
+    pub fn as_str(&self) -> &str {
+        &self.canonical
+    }
+
+    /// Get the Package URL.
+    pub fn purl(&self) -> &PackageUrl<'a> {
+        &self.purl
+    }
+
+    /// Discard the cached string and get the Package URL back.
+    pub fn into_inner(self) -> PackageUrl<'a> {
+        self.purl
+    }
+}
+
+impl<'a> From<PackageUrl<'a>> for CachedPackageUrl<'a> {
+    fn from(purl: PackageUrl<'a>) -> Self {
+        Self::new(purl)
+    }
+}
+
+impl<'a> Deref for CachedPackageUrl<'a> {
+    type Target = PackageUrl<'a>;
+    fn deref(&self) -> &Self::Target {
+        &self.purl
+    }
+}
+
+impl AsRef<str> for CachedPackageUrl<'_> {
+    fn as_ref(&self) -> &str {
+        &self.canonical
+    }
+}
+
+impl Borrow<str> for CachedPackageUrl<'_> {
+    fn borrow(&self) -> &str {
+        &self.canonical
+    }
+}
+
+impl PartialEq for CachedPackageUrl<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.canonical == other.canonical
+    }
+}
+
+impl Eq for CachedPackageUrl<'_> {}
+
+impl PartialOrd for CachedPackageUrl<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CachedPackageUrl<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.canonical.cmp(&other.canonical)
+    }
+}
+
+impl Hash for CachedPackageUrl<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical.hash(state)
+    }
+}
+
+impl Display for CachedPackageUrl<'_> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(&self.canonical)
+    }
+}
@@ -0,0 +1,271 @@
+use alloc::format;
+use alloc::string::String;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+
+use super::errors::Error;
+use super::errors::ErrorKind;
+use super::utils::QuickFind;
+use super::validation;
+
+/// A human-readable report explaining why a Package URL is invalid.
+///
+/// The report shows the offending part of the input, the rule of the
+/// specification that was broken and, when possible, a suggested fix.
+///
+/// # Example
+/// ```rust
+/// # extern crate packageurl;
+/// use packageurl::{Diagnostic, PackageUrl};
+///
+/// let input = "pkg:npm/@angular/core";
+/// let err = PackageUrl::parse(input).unwrap_err();
+/// let diagnostic = Diagnostic::new(input, &err);
+/// assert_eq!(
+///     diagnostic.help().as_deref(),
+///     Some("percent-encode the '@' as '%40': `pkg:npm/%40angular/core`"),
+/// );
+/// println!("{}", diagnostic);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Diagnostic<'e> {
+    input: &'e str,
+    error: &'e Error,
+}
+
+impl<'e> Diagnostic<'e> {
+    /// Create a report for an error obtained while parsing `input`.
+    pub fn new(input: &'e str, error: &'e Error) -> Self {
+        Self { input, error }
+    }
+
+    /// Get the error being reported.
+    pub fn error(&self) -> &'e Error {
+        self.error
+    }
+
+    /// Explain the rule of the specification that the input breaks.
+    pub fn note(&self) -> Option<&'static str> {
+        match self.error.kind() {
+            ErrorKind::InvalidScheme(_) => Some("the scheme of a Package URL is always 'pkg'"),
+            ErrorKind::MissingScheme => Some("a Package URL must start with the 'pkg:' scheme"),
+            ErrorKind::InvalidType(ty) => validation::type_violation(ty),
+            ErrorKind::MissingType => Some("the scheme must be followed by a type and a '/'"),
+            ErrorKind::InvalidKey(key) => validation::qualifier_key_violation(key),
+            ErrorKind::MissingName => Some("a name is required after the type and namespace"),
+            ErrorKind::InvalidName(_) => Some("the name does not follow the rules of the type"),
+            ErrorKind::MissingNamespace(_) => Some("the type definition requires a namespace"),
+            ErrorKind::ProhibitedNamespace(_) => {
+                Some("the type definition does not allow a namespace")
+            }
+            ErrorKind::InvalidNamespaceComponent(c) => validation::namespace_component_violation(c),
+            ErrorKind::InvalidSubpathSegment(s) => validation::subpath_segment_violation(s),
+            ErrorKind::EmptyQualifierValue(_) => Some("qualifiers must have a non-empty value"),
+            ErrorKind::MissingQualifier(_) => Some("the type requires this qualifier"),
+            ErrorKind::InvalidVersion(_) => {
+                Some("the version does not follow the rules of the type")
+            }
+            ErrorKind::InvalidCoordinates(_) => {
+                Some("the coordinates do not follow the format of the ecosystem")
+            }
+            ErrorKind::DuplicateKey(_) => Some("qualifier keys must be unique"),
+            ErrorKind::InputTooLong(_) | ErrorKind::TooManyQualifiers(_) => {
+                Some("the input exceeds a limit set in the parse options")
+            }
+            ErrorKind::DecodingError(_) => {
+                Some("percent-encoded octets must decode to valid UTF-8")
+            }
+            ErrorKind::Multiple(_) => None,
+        }
+    }
+
+    /// Suggest a fix for the input, if one is known.
+    pub fn help(&self) -> Option<String> {
+        match self.error.kind() {
+            ErrorKind::InvalidScheme(scheme) if scheme.eq_ignore_ascii_case("pkg") => self
+                .input
+                .strip_prefix(scheme.as_str())
+                .map(|rest| format!("write the scheme in lowercase: `pkg{}`", rest)),
+            ErrorKind::MissingScheme => Some(format!("add the scheme: `pkg:{}`", self.input)),
+            ErrorKind::InvalidKey(key) if key.starts_with(|c: char| c.is_ascii_digit()) => {
+                Some("rename the key so that it starts with an ASCII letter".into())
+            }
+            ErrorKind::MissingName => match self.unencoded_at() {
+                Some(i) => Some(format!(
+                    "percent-encode the '@' as '%40': `{}%40{}`",
+                    &self.input[..i],
+                    &self.input[i + 1..]
+                )),
+                None => Some("add a name after the type, such as `pkg:type/name`".into()),
+            },
+            ErrorKind::InvalidNamespaceComponent(c) | ErrorKind::InvalidSubpathSegment(c) => {
+                if c.is_empty() || c == "." || c == ".." {
+                    Some("remove the segment from the path".into())
+                } else {
+                    Some("do not percent-encode the '/' as '%2F'".into())
+                }
+            }
+            ErrorKind::EmptyQualifierValue(_) => Some("remove the qualifier".into()),
+            ErrorKind::DuplicateKey(_) => Some("keep a single value for the key".into()),
+            ErrorKind::DecodingError(_) => Some("encode a literal '%' as '%25'".into()),
+            _ => None,
+        }
+    }
+
+    /// Locate an '@' that was likely meant to be part of a namespace.
+    ///
+    /// If the text following the last '@' contains a '/', the '@' was parsed
+    /// as the version separator while it probably starts an npm scope.
+    fn unencoded_at(&self) -> Option<usize> {
+        let end = self
+            .input
+            .quickfind(b'?')
+            .or_else(|| self.input.quickfind(b'#'))
+            .unwrap_or(self.input.len());
+        let i = (&self.input[..end]).quickrfind(b'@')?;
+        if self.input[i + 1..end].contains('/') && self.input[..i].ends_with('/') {
+            Some(i)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        if let ErrorKind::Multiple(errors) = self.error.kind() {
+            for (i, error) in errors.iter().enumerate() {
+                if i > 0 {
+                    f.write_str("\n")?;
+                }
+                Diagnostic::new(self.input, error).fmt(f)?;
+            }
+            return Ok(());
+        }
+
+        writeln!(f, "error: {}", self.error)?;
+        if let Some(span) = self.error.span() {
+            let start = span.start.min(self.input.len());
+            let end = span.end.clamp(start, self.input.len());
+            let offset = self.input[..start].chars().count();
+            let width = self.input[start..end].chars().count().max(1);
+            writeln!(f, "  |")?;
+            writeln!(f, "  | {}", self.input)?;
+            write!(f, "  | {:offset$}{:^<width$}", "", "")?;
+            match self.error.component() {
+                Some(component) => writeln!(f, " invalid {}", component)?,
+                None => writeln!(f)?,
+            }
+            writeln!(f, "  |")?;
+        } else if let Some(component) = self.error.component() {
+            writeln!(f, "  = in: {}", component)?;
+        }
+        if let Some(note) = self.note() {
+            writeln!(f, "  = note: {}", note)?;
+        }
+        if let Some(help) = self.help() {
+            writeln!(f, "  = help: {}", help)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use alloc::string::ToString;
+
+    use crate::PackageUrl;
+
+    use super::*;
+
+    fn render(input: &str) -> String {
+        let err = PackageUrl::parse(input).unwrap_err();
+        Diagnostic::new(input, &err).to_string()
+    }
+
+    #[test]
+    fn test_invalid_key() {
+        assert_eq!(
+            render("pkg:type/name?1st-key=value"),
+            concat!(
+                "error: invalid key: \"1st-key\"\n",
+                "  |\n",
+                "  | pkg:type/name?1st-key=value\n",
+                "  |               ^^^^^^^ invalid qualifier\n",
+                "  |\n",
+                "  = note: qualifier keys must not start with a digit\n",
+                "  = help: rename the key so that it starts with an ASCII letter\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_unencoded_scope() {
+        assert_eq!(
+            render("pkg:npm/@angular/core?x=y"),
+            concat!(
+                "error: missing name\n",
+                "  |\n",
+                "  | pkg:npm/@angular/core?x=y\n",
+                "  |         ^ invalid name\n",
+                "  |\n",
+                "  = note: a name is required after the type and namespace\n",
+                "  = help: percent-encode the '@' as '%40': `pkg:npm/%40angular/core?x=y`\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_missing_scheme() {
+        let input = "npm/left-pad";
+        let err = PackageUrl::parse(input).unwrap_err();
+        let diagnostic = Diagnostic::new(input, &err);
+        assert_eq!(
+            diagnostic.help().as_deref(),
+            Some("add the scheme: `pkg:npm/left-pad`")
+        );
+    }
+
+    #[test]
+    fn test_uppercase_scheme() {
+        let err = PackageUrl::parse("PKG:type/name").unwrap_err();
+        let diagnostic = Diagnostic::new("PKG:type/name", &err);
+        assert_eq!(
+            diagnostic.help().as_deref(),
+            Some("write the scheme in lowercase: `pkg:type/name`")
+        );
+
+        let diagnostic = Diagnostic::new("", &err);
+        assert_eq!(diagnostic.help(), None);
+        assert_eq!(
+            diagnostic.to_string(),
+            concat!(
+                "error: invalid scheme: \"PKG\"\n",
+                "  |\n",
+                "  | \n",
+                "  | ^ invalid scheme\n",
+                "  |\n",
+                "  = note: the scheme of a Package URL is always 'pkg'\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_without_span() {
+        let err = PackageUrl::builder("type", "name")
+            .subpath("a/../b")
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            Diagnostic::new("", &err).to_string(),
+            concat!(
+                "error: invalid subpath segment: \"..\"\n",
+                "  = in: subpath\n",
+                "  = note: subpath segments must not be '.' or '..'\n",
+                "  = help: remove the segment from the path\n",
+            )
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use alloc::borrow::Cow;
+
+use crate::errors::Component;
+use crate::errors::Error;
+use crate::errors::ErrorKind;
+use crate::errors::Result;
+use crate::purl::PackageUrl;
+use crate::purl_type::PurlType;
+use crate::rules::TypeRules;
+
+/// The rules of the `cpan` type.
+///
+/// With an author as namespace, the name is a distribution name such as
+/// `URI-PackageURL`; without one, it is a module name such as
+/// `URI::PackageURL`.
+pub(crate) struct CpanRules;
+
+impl TypeRules for CpanRules {
+    normalize_as_defined!(PurlType::Cpan);
+
+    fn validate(&self, purl: &PackageUrl) -> Result<()> {
+        let name = purl.name();
+        let invalid = match purl.namespace() {
+            Some(_) => name.contains("::"),
+            None => name.contains('-'),
+        };
+        if invalid {
+            return Err(
+                Error::new(ErrorKind::InvalidName(name.into())).in_component(Component::Name)
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(PackageUrl::parse("pkg:cpan/DROLSKY/DateTime@1.55").is_ok());
+        assert!(PackageUrl::parse("pkg:cpan/URI::PackageURL@2.11").is_ok());
+        let err = PackageUrl::parse("pkg:cpan/Perl-Version@1.013").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::InvalidName("Perl-Version".into()));
+        let err = PackageUrl::parse("pkg:cpan/GDT/URI::PackageURL@2").unwrap_err();
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::InvalidName("URI::PackageURL".into())
+        );
+    }
+}
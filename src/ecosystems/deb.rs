@@ -0,0 +1,363 @@
+use alloc::borrow::Cow;
+use alloc::format;
+
+use crate::errors::Component;
+use crate::errors::Error;
+use crate::errors::ErrorKind;
+use crate::errors::Result;
+use crate::purl::PackageUrl;
+use crate::purl_type::PurlType;
+use crate::rules::TypeRules;
+
+/// The rules of the `deb` type.
+pub(crate) struct DebRules;
+
+impl TypeRules for DebRules {
+    normalize_as_defined!(
+        PurlType::Deb,
+        normalize_namespace: namespace,
+        normalize_name: name
+    );
+
+    fn normalize_version<'a>(&self, version: Cow<'a, str>) -> Cow<'a, str> {
+        // dpkg considers a zero epoch equal to no epoch at all
+        let start = match version.split_once(':') {
+            Some((epoch, _)) if !epoch.is_empty() && epoch.bytes().all(|b| b == b'0') => {
+                epoch.len() + 1
+            }
+            _ => return version,
+        };
+        match version {
+            Cow::Borrowed(v) => Cow::Borrowed(&v[start..]),
+            Cow::Owned(mut v) => {
+                v.drain(..start);
+                Cow::Owned(v)
+            }
+        }
+    }
+
+    fn validate(&self, purl: &PackageUrl) -> Result<()> {
+        match purl.version() {
+            Some(version) if !is_version_valid(version) => {
+                Err(Error::new(ErrorKind::InvalidVersion(version.into()))
+                    .in_component(Component::Version))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Check that a version has a numeric epoch, if any, and an upstream version.
+fn is_version_valid(version: &str) -> bool {
+    match version.split_once(':') {
+        Some((epoch, upstream)) => {
+            !epoch.is_empty() && epoch.bytes().all(|b| b.is_ascii_digit()) && !upstream.is_empty()
+        }
+        None => true,
+    }
+}
+
+/// The fields of a dpkg stanza describing a package.
+#[derive(Default)]
+struct Stanza<'a> {
+    package: Option<&'a str>,
+    version: Option<&'a str>,
+    architecture: Option<&'a str>,
+    source: Option<&'a str>,
+    status: Option<&'a str>,
+}
+
+impl<'a> Stanza<'a> {
+    fn parse(text: &'a str) -> Result<Self> {
+        let mut stanza = Self::default();
+        for line in text.lines() {
+            // skip the continuation lines of multiline fields
+            if line.starts_with([' ', '\t']) || line.trim().is_empty() {
+                continue;
+            }
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| Error::new(ErrorKind::InvalidCoordinates(line.into())))?;
+            let value = Some(value.trim()).filter(|v| !v.is_empty());
+            if key.eq_ignore_ascii_case("Package") {
+                stanza.package = value;
+            } else if key.eq_ignore_ascii_case("Version") {
+                stanza.version = value;
+            } else if key.eq_ignore_ascii_case("Architecture") {
+                stanza.architecture = value;
+            } else if key.eq_ignore_ascii_case("Source") {
+                stanza.source = value;
+            } else if key.eq_ignore_ascii_case("Status") {
+                stanza.status = value;
+            }
+        }
+        Ok(stanza)
+    }
+
+    /// Check whether the package is installed, according to its status.
+    fn is_installed(&self) -> bool {
+        self.status.map_or(true, |status| {
+            status.split_whitespace().nth(2) == Some("installed")
+        })
+    }
+}
+
+impl<'a> PackageUrl<'a> {
+    /// Create a `deb` Package URL from a stanza of the dpkg status file.
+    ///
+    /// The `Package`, `Version` and `Architecture` fields give the name, the
+    /// version and the `arch` qualifier. The `Source` field, if any, names
+    /// the source package the binary package was built from: it is recorded
+    /// in the `source` qualifier, as `name` or `name@version` when the source
+    /// version differs. See [`PackageUrl::to_deb_source`] to get the Package
+    /// URL of the source package.
+    ///
+    /// The distribution is recorded in the `distro` qualifier, and its name,
+    /// up to the first '-', is used as the namespace.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// use packageurl::PackageUrl;
+    ///
+    /// let stanza = "\
+    /// Package: libc6
+    /// Status: install ok installed
+    /// Architecture: amd64
+    /// Source: glibc
+    /// Version: 2.35-0ubuntu3.1
+    /// Description: GNU C Library: Shared libraries
+    ///  Contains the standard libraries that are used by nearly all programs.
+    /// ";
+    /// let purl = PackageUrl::from_dpkg_stanza(stanza, "ubuntu-22.04").unwrap();
+    /// assert_eq!(
+    ///     purl.to_string(),
+    ///     "pkg:deb/ubuntu/libc6@2.35-0ubuntu3.1?arch=amd64&distro=ubuntu-22.04&source=glibc"
+    /// );
+    /// ```
+    pub fn from_dpkg_stanza(stanza: &'a str, distro: &'a str) -> Result<Self> {
+        Self::from_stanza(Stanza::parse(stanza)?, distro)
+    }
+
+    /// Create `deb` Package URLs for the packages installed according to
+    /// the dpkg status file, usually found at `/var/lib/dpkg/status`.
+    ///
+    /// Stanzas are separated by empty lines, and converted as described in
+    /// [`PackageUrl::from_dpkg_stanza`]. Packages that are not installed
+    /// according to their `Status` field are skipped.
+    pub fn from_dpkg_status(
+        status: &'a str,
+        distro: &'a str,
+    ) -> impl Iterator<Item = Result<Self>> + 'a {
+        status
+            .split("\n\n")
+            .filter(|text| !text.trim().is_empty())
+            .filter_map(move |text| match Stanza::parse(text) {
+                Ok(stanza) if stanza.is_installed() => Some(Self::from_stanza(stanza, distro)),
+                Ok(_) => None,
+                Err(e) => Some(Err(e)),
+            })
+    }
+
+    /// Create a `deb` Package URL from a line listed by `dpkg-query -W`.
+    ///
+    /// The line is made of the package name, followed by `:arch` for
+    /// packages installed for several architectures, and of the version,
+    /// separated by whitespace. The architecture can also be given as a
+    /// third field, e.g. with `dpkg-query -W -f '${Package} ${Version}
+    /// ${Architecture}\n'`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// use packageurl::PackageUrl;
+    ///
+    /// let purl = PackageUrl::from_dpkg_query("gnome-calculator:amd64\t1:41.1-2ubuntu2", "ubuntu")
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     purl.to_string(),
+    ///     "pkg:deb/ubuntu/gnome-calculator@1%3A41.1-2ubuntu2?arch=amd64&distro=ubuntu"
+    /// );
+    /// ```
+    pub fn from_dpkg_query(line: &'a str, distro: &'a str) -> Result<Self> {
+        let mut fields = line.split_whitespace();
+        let (package, architecture) = match fields.next() {
+            Some(field) => match field.split_once(':') {
+                Some((package, arch)) => (package, Some(arch)),
+                None => (field, None),
+            },
+            None => return Err(Error::new(ErrorKind::InvalidCoordinates(line.into()))),
+        };
+        let stanza = Stanza {
+            package: Some(package),
+            version: fields.next(),
+            architecture: fields.next().or(architecture),
+            ..Stanza::default()
+        };
+        if fields.next().is_some() {
+            return Err(Error::new(ErrorKind::InvalidCoordinates(line.into())));
+        }
+        Self::from_stanza(stanza, distro)
+    }
+
+    /// Get the Package URL of the source package of a `deb` Package URL.
+    ///
+    /// The source package is named by the `source` qualifier, and defaults
+    /// to the package itself. It has the `source` architecture, and keeps the
+    /// namespace and the `distro` qualifier. Returns `None` for other types.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// use packageurl::PackageUrl;
+    ///
+    /// let purl = PackageUrl::parse("pkg:deb/debian/libc6@2.36-9?arch=amd64&source=glibc")
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     purl.to_deb_source().unwrap().to_string(),
+    ///     "pkg:deb/debian/glibc@2.36-9?arch=source"
+    /// );
+    /// ```
+    pub fn to_deb_source(&self) -> Option<PackageUrl<'_>> {
+        if self.purl_type() != &PurlType::Deb {
+            return None;
+        }
+        let (name, version) = match self.qualifiers().get("source") {
+            Some(source) => match source.split_once('@') {
+                Some((name, version)) => (name, Some(version)),
+                None => (source, self.version()),
+            },
+            None => (self.name(), self.version()),
+        };
+        let mut builder = PackageUrl::builder("deb", name);
+        if let Some(namespace) = self.namespace() {
+            builder = builder.namespace(namespace);
+        }
+        if let Some(version) = version {
+            builder = builder.version(version);
+        }
+        builder = builder.qualifier("arch", "source");
+        if let Some(distro) = self.qualifiers().get("distro") {
+            builder = builder.qualifier("distro", distro);
+        }
+        builder.build().ok()
+    }
+
+    fn from_stanza(stanza: Stanza<'a>, distro: &'a str) -> Result<Self> {
+        let package = stanza
+            .package
+            .ok_or_else(|| Error::new(ErrorKind::MissingName).in_component(Component::Name))?;
+        let mut builder = PackageUrl::builder("deb", package).namespace(super::vendor(distro));
+        if let Some(version) = stanza.version {
+            builder = builder.version(version);
+        }
+        if let Some(architecture) = stanza.architecture {
+            builder = builder.qualifier("arch", architecture);
+        }
+        builder = builder.qualifier("distro", distro);
+        if let Some(source) = stanza.source {
+            // the source version is only given when it differs
+            let source = match source.split_once(' ') {
+                Some((name, version)) => {
+                    let version = version.trim().trim_start_matches('(').trim_end_matches(')');
+                    Cow::Owned(format!("{}@{}", name, version))
+                }
+                None => Cow::Borrowed(source),
+            };
+            builder = builder.qualifier("source", source);
+        }
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    const STATUS: &str = "\
+Package: libc6
+Status: install ok installed
+Architecture: amd64
+Multi-Arch: same
+Source: glibc (2.36-9+deb12u1)
+Version: 2.36-9+deb12u1+b1
+Description: GNU C Library: Shared libraries
+ Contains the standard libraries that are used by nearly all programs on
+ the system.
+
+Package: old-tool
+Status: deinstall ok config-files
+Architecture: all
+Version: 1.0-1
+
+Package: gnome-calculator
+Status: install ok installed
+Architecture: amd64
+Version: 1:43.0.1-2
+";
+
+    #[test]
+    fn test_from_dpkg_status() {
+        let purls = PackageUrl::from_dpkg_status(STATUS, "debian-12")
+            .map(|purl| purl.unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            purls,
+            [
+                "pkg:deb/debian/libc6@2.36-9%2Bdeb12u1%2Bb1?arch=amd64&distro=debian-12&source=glibc%402.36-9%2Bdeb12u1",
+                "pkg:deb/debian/gnome-calculator@1%3A43.0.1-2?arch=amd64&distro=debian-12",
+            ]
+        );
+
+        let err = PackageUrl::from_dpkg_stanza("Version: 1.0", "debian").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::MissingName);
+        let err = PackageUrl::from_dpkg_stanza("Package: a\nVersion: x:1.0", "debian").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::InvalidVersion("x:1.0".into()));
+    }
+
+    #[test]
+    fn test_from_dpkg_query() {
+        let purl = PackageUrl::from_dpkg_query("adduser 3.118 all", "debian").unwrap();
+        assert_eq!(
+            purl.to_string(),
+            "pkg:deb/debian/adduser@3.118?arch=all&distro=debian"
+        );
+        let purl = PackageUrl::from_dpkg_query("Zlib1g\t", "debian").unwrap();
+        assert_eq!(purl.to_string(), "pkg:deb/debian/zlib1g?distro=debian");
+        assert!(PackageUrl::from_dpkg_query("", "debian").is_err());
+        assert!(PackageUrl::from_dpkg_query("a 1.0 all extra", "debian").is_err());
+    }
+
+    #[test]
+    fn test_to_deb_source() {
+        let purl = PackageUrl::from_dpkg_status(STATUS, "debian-12")
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            purl.to_deb_source().unwrap().to_string(),
+            "pkg:deb/debian/glibc@2.36-9%2Bdeb12u1?arch=source&distro=debian-12"
+        );
+        let purl = PackageUrl::parse("pkg:deb/debian/curl@7.50.3-1?arch=i386").unwrap();
+        assert_eq!(
+            purl.to_deb_source().unwrap().to_string(),
+            "pkg:deb/debian/curl@7.50.3-1?arch=source"
+        );
+        let purl = PackageUrl::parse("pkg:rpm/fedora/curl@7.50.3-1").unwrap();
+        assert!(purl.to_deb_source().is_none());
+    }
+
+    #[test]
+    fn test_epoch() {
+        let purl = PackageUrl::parse("pkg:deb/ubuntu/gnome-calculator@0:41.1-2ubuntu2").unwrap();
+        assert_eq!(purl.version(), Some("41.1-2ubuntu2"));
+        let purl = PackageUrl::parse("pkg:deb/ubuntu/gnome-calculator@1:41.1-2ubuntu2").unwrap();
+        assert_eq!(purl.version(), Some("1:41.1-2ubuntu2"));
+        let err = PackageUrl::parse("pkg:deb/ubuntu/gnome-calculator@:41.1").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::InvalidVersion(":41.1".into()));
+    }
+}
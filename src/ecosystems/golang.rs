@@ -0,0 +1,421 @@
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+
+use crate::errors::Component;
+use crate::errors::Error;
+use crate::errors::ErrorKind;
+use crate::errors::Result;
+use crate::purl::PackageUrl;
+use crate::purl_type::PurlType;
+use crate::rules::TypeRules;
+
+/// The build suffix of a major version adopted before Go modules.
+const INCOMPATIBLE: &str = "+incompatible";
+
+/// The number of digits of the timestamp of a pseudo-version.
+const TIMESTAMP_LEN: usize = 14;
+
+/// The rules of the `golang` type.
+///
+/// The native coordinates are built from the lowercased namespace, see
+/// [`PackageUrl::go_lowercase_module_path`].
+pub(crate) struct GolangRules;
+
+impl TypeRules for GolangRules {
+    normalize_as_defined!(PurlType::Golang);
+
+    fn validate(&self, purl: &PackageUrl) -> Result<()> {
+        let uppercase = purl
+            .namespace_segments()
+            .find(|segment| segment.chars().any(char::is_uppercase));
+        match uppercase {
+            Some(segment) => Err(
+                Error::new(ErrorKind::InvalidNamespaceComponent(segment.into()))
+                    .in_component(Component::Namespace),
+            ),
+            None => Ok(()),
+        }
+    }
+
+    fn native_coordinates(&self, purl: &PackageUrl) -> Option<String> {
+        let path = purl.go_lowercase_module_path()?;
+        match purl.version() {
+            Some(version) => Some(format!("{}@{}", path, version)),
+            None => Some(path),
+        }
+    }
+}
+
+/// A Go pseudo-version, naming a revision of a module without a tag.
+///
+/// Pseudo-versions have one of three forms, depending on the latest tag
+/// before the revision:
+/// - `vX.0.0-yyyymmddhhmmss-abcdefabcdef` when there is no tag,
+/// - `vX.Y.Z-pre.0.yyyymmddhhmmss-abcdefabcdef` after a `vX.Y.Z-pre` tag,
+/// - `vX.Y.(Z+1)-0.yyyymmddhhmmss-abcdefabcdef` after a `vX.Y.Z` tag,
+///
+/// and may be followed by `+incompatible`.
+///
+/// # Example
+/// ```rust
+/// # extern crate packageurl;
+/// use packageurl::GoPseudoVersion;
+///
+/// let version = GoPseudoVersion::parse("v1.4.1-0.20210101120000-abcdef123456").unwrap();
+/// assert_eq!(version.base(), Some("v1.4.0"));
+/// assert_eq!(version.timestamp(), "20210101120000");
+/// assert_eq!(version.commit(), "abcdef123456");
+/// assert!(!version.is_incompatible());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GoPseudoVersion<'a> {
+    base: Option<Cow<'a, str>>,
+    timestamp: &'a str,
+    commit: &'a str,
+    incompatible: bool,
+}
+
+impl<'a> GoPseudoVersion<'a> {
+    /// Parse a pseudo-version, or return `None` if the version is not one.
+    pub fn parse(version: &'a str) -> Option<Self> {
+        let (version, incompatible) = match version.strip_suffix(INCOMPATIBLE) {
+            Some(version) => (version, true),
+            None => (version, false),
+        };
+        let (rest, commit) = version.rsplit_once('-')?;
+        if commit.is_empty() || !commit.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return None;
+        }
+        let (head, timestamp) = rest.split_at_checked(rest.len().checked_sub(TIMESTAMP_LEN)?)?;
+        if !timestamp.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        let base = if let Some(core) = head.strip_suffix('-') {
+            // no tag before the revision: vX.0.0
+            match parse_core(core)? {
+                (_, "0", "0") => None,
+                _ => return None,
+            }
+        } else if let Some(core) = head.strip_suffix("-0.") {
+            // after a release tag: the patch was incremented
+            let (major, minor, patch) = parse_core(core)?;
+            let patch = patch.parse::<u64>().ok()?.checked_sub(1)?;
+            Some(Cow::Owned(format!("v{}.{}.{}", major, minor, patch)))
+        } else {
+            // after a pre-release tag: vX.Y.Z-pre
+            let tag = head.strip_suffix(".0.")?;
+            let (core, pre) = tag.split_once('-')?;
+            parse_core(core)?;
+            if pre.is_empty() {
+                return None;
+            }
+            Some(Cow::Borrowed(tag))
+        };
+
+        Some(Self {
+            base,
+            timestamp,
+            commit,
+            incompatible,
+        })
+    }
+
+    /// Get the version of the latest tag before the revision, if any.
+    pub fn base(&self) -> Option<&str> {
+        self.base.as_deref()
+    }
+
+    /// Get the UTC commit time of the revision, as `yyyymmddhhmmss`.
+    pub fn timestamp(&self) -> &'a str {
+        self.timestamp
+    }
+
+    /// Get the abbreviated commit hash of the revision.
+    pub fn commit(&self) -> &'a str {
+        self.commit
+    }
+
+    /// Check whether the version has the `+incompatible` suffix.
+    pub fn is_incompatible(&self) -> bool {
+        self.incompatible
+    }
+}
+
+/// Split a `vX.Y.Z` version into its numeric parts.
+fn parse_core(core: &str) -> Option<(&str, &str, &str)> {
+    let mut parts = core.strip_prefix('v')?.split('.');
+    let (major, minor, patch) = (parts.next()?, parts.next()?, parts.next()?);
+    let is_number = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    match parts.next() {
+        None if is_number(major) && is_number(minor) && is_number(patch) => {
+            Some((major, minor, patch))
+        }
+        _ => None,
+    }
+}
+
+impl<'a> PackageUrl<'a> {
+    /// Create a `golang` Package URL for a Go module.
+    ///
+    /// The last element of the module path is the name, and the elements
+    /// before it are the namespace, which is lowercased. Packages inside the
+    /// module are named by the subpath of the Package URL.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// use packageurl::PackageUrl;
+    ///
+    /// let mut purl = PackageUrl::from_go_module("google.golang.org/genproto", None).unwrap();
+    /// purl.with_subpath("googleapis/api/annotations").unwrap();
+    /// assert_eq!(
+    ///     purl.to_string(),
+    ///     "pkg:golang/google.golang.org/genproto#googleapis/api/annotations"
+    /// );
+    /// assert_eq!(
+    ///     purl.go_lowercase_import_path().as_deref(),
+    ///     Some("google.golang.org/genproto/googleapis/api/annotations")
+    /// );
+    /// ```
+    pub fn from_go_module(path: &'a str, version: Option<&'a str>) -> Result<Self> {
+        let invalid = || Error::new(ErrorKind::InvalidCoordinates(path.into()));
+        let (namespace, name) = match path.rsplit_once('/') {
+            Some((namespace, name)) => (Some(namespace), name),
+            None => (None, path),
+        };
+        if name.is_empty() || matches!(name, "(" | ")") {
+            return Err(invalid());
+        }
+
+        let mut purl = PackageUrl::new("golang", name)?;
+        if let Some(namespace) = namespace {
+            purl.with_namespace(namespace)?;
+        }
+        if let Some(version) = version {
+            purl.with_version(version)?;
+        }
+        Ok(purl)
+    }
+
+    /// Create a `golang` Package URL from a line of a `go.mod` file.
+    ///
+    /// The line is either a `require` or a `module` directive, or a line of
+    /// a `require` block, i.e. a module path followed by a version. Comments
+    /// such as `// indirect` are ignored, and other directives are rejected.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// use packageurl::PackageUrl;
+    ///
+    /// let purl = PackageUrl::from_go_mod_line("\tgithub.com/gorilla/context v1.1.1 // indirect")
+    ///     .unwrap();
+    /// assert_eq!(purl.to_string(), "pkg:golang/github.com/gorilla/context@v1.1.1");
+    /// ```
+    pub fn from_go_mod_line(line: &'a str) -> Result<Self> {
+        let invalid = || Error::new(ErrorKind::InvalidCoordinates(line.into()));
+        let directive = match line.split_once("//") {
+            Some((directive, _)) => directive,
+            None => line,
+        };
+        let mut fields = directive.split_whitespace();
+        let (path, version) = match fields.next() {
+            Some("module") => (fields.next(), None),
+            Some("require") => (fields.next(), Some(fields.next().ok_or_else(invalid)?)),
+            Some("go" | "toolchain" | "godebug" | "replace" | "exclude" | "retract") => {
+                return Err(invalid())
+            }
+            Some(path) => (Some(path), Some(fields.next().ok_or_else(invalid)?)),
+            None => (None, None),
+        };
+        match path {
+            Some(path) if fields.next().is_none() => {
+                Self::from_go_module(path.trim_matches('"'), version).map_err(|_| invalid())
+            }
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Create a `golang` Package URL from a line of a `go.sum` file.
+    ///
+    /// The line is made of a module path, a version, possibly followed by
+    /// `/go.mod` for the checksum of the `go.mod` file only, and a checksum,
+    /// which is ignored.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// use packageurl::PackageUrl;
+    ///
+    /// let line = "github.com/pkg/errors v0.9.1/go.mod h1:bwawxfHBFNV+L2hUp1rHADufV3IMtnDRdf1r5NINEl0=";
+    /// let purl = PackageUrl::from_go_sum_line(line).unwrap();
+    /// assert_eq!(purl.to_string(), "pkg:golang/github.com/pkg/errors@v0.9.1");
+    /// ```
+    pub fn from_go_sum_line(line: &'a str) -> Result<Self> {
+        let invalid = || Error::new(ErrorKind::InvalidCoordinates(line.into()));
+        let mut fields = line.split_whitespace();
+        match (fields.next(), fields.next(), fields.next(), fields.next()) {
+            (Some(path), Some(version), Some(_), None) => {
+                let version = version.strip_suffix("/go.mod").unwrap_or(version);
+                Self::from_go_module(path, Some(version)).map_err(|_| invalid())
+            }
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Get the module path of a `golang` Package URL, with the namespace in
+    /// lowercase.
+    ///
+    /// The namespace is lowercased when creating the Package URL, so the
+    /// original case of the module path is lost: `github.com/Azure/go-autorest`
+    /// is returned as `github.com/azure/go-autorest`. Module paths are case
+    /// sensitive, so the result can only be used to fetch a module whose path
+    /// has no uppercase letters in the namespace.
+    ///
+    /// Returns `None` for other types.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// use packageurl::PackageUrl;
+    ///
+    /// let purl = PackageUrl::from_go_module("github.com/Azure/go-autorest", None).unwrap();
+    /// assert_eq!(
+    ///     purl.go_lowercase_module_path().as_deref(),
+    ///     Some("github.com/azure/go-autorest")
+    /// );
+    /// ```
+    pub fn go_lowercase_module_path(&self) -> Option<String> {
+        if self.purl_type() != &PurlType::Golang {
+            return None;
+        }
+        match self.namespace() {
+            Some(namespace) => Some(format!("{}/{}", namespace, self.name())),
+            None => Some(String::from(self.name())),
+        }
+    }
+
+    /// Get the import path of the package named by a `golang` Package URL,
+    /// i.e. the module path followed by the subpath, if any.
+    ///
+    /// Like [`PackageUrl::go_lowercase_module_path`], the namespace is in
+    /// lowercase. Returns `None` for other types.
+    pub fn go_lowercase_import_path(&self) -> Option<String> {
+        let mut path = self.go_lowercase_module_path()?;
+        if let Some(subpath) = self.subpath() {
+            path.push('/');
+            path.push_str(subpath);
+        }
+        Some(path)
+    }
+
+    /// Get the pseudo-version of a `golang` Package URL, if its version is
+    /// one.
+    ///
+    /// Returns `None` for other types.
+    pub fn go_pseudo_version(&self) -> Option<GoPseudoVersion<'_>> {
+        match self.purl_type() {
+            PurlType::Golang => GoPseudoVersion::parse(self.version()?),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_pseudo_version() {
+        let version = GoPseudoVersion::parse("v0.0.0-20210101000000-abcdef123456").unwrap();
+        assert_eq!(version.base(), None);
+        assert_eq!(version.timestamp(), "20210101000000");
+        assert_eq!(version.commit(), "abcdef123456");
+
+        let version = GoPseudoVersion::parse("v1.2.3-rc.1.0.20210101000000-abcdef123456").unwrap();
+        assert_eq!(version.base(), Some("v1.2.3-rc.1"));
+
+        let version =
+            GoPseudoVersion::parse("v2.0.1-0.20180101000000-abcdef123456+incompatible").unwrap();
+        assert_eq!(version.base(), Some("v2.0.0"));
+        assert!(version.is_incompatible());
+
+        for version in [
+            "v1.2.3",
+            "v2.0.0+incompatible",
+            "v1.2.0-20210101000000-abcdef123456",
+            "v1.2.0-0.20210101000000-abcdef123456",
+            "v0.0.0-2021010100000-abcdef123456",
+            "v0.0.0-20210101000000-",
+            "1.0.0-20210101000000-abcdef123456",
+        ] {
+            assert_eq!(GoPseudoVersion::parse(version), None, "{}", version);
+        }
+    }
+
+    #[test]
+    fn test_from_go_mod_line() {
+        let cases = [
+            ("module example.com/App", "pkg:golang/example.com/App"),
+            (
+                "require github.com/Azure/go-autorest v14.2.0+incompatible",
+                "pkg:golang/github.com/azure/go-autorest@v14.2.0%2Bincompatible",
+            ),
+            (
+                "\tgolang.org/x/sys v0.0.0-20210124154548-22da62e12c0c // indirect",
+                "pkg:golang/golang.org/x/sys@v0.0.0-20210124154548-22da62e12c0c",
+            ),
+        ];
+        for (line, expected) in cases {
+            let purl = PackageUrl::from_go_mod_line(line).unwrap();
+            assert_eq!(purl.to_string(), expected);
+        }
+        for line in ["require (", ")", "", "go 1.21", "replace a => b v1.0.0"] {
+            let err = PackageUrl::from_go_mod_line(line).unwrap_err();
+            assert_eq!(err.kind(), &ErrorKind::InvalidCoordinates(line.into()));
+        }
+    }
+
+    #[test]
+    fn test_from_go_sum_line() {
+        let line = "golang.org/x/sys v0.0.0-20210124154548-22da62e12c0c h1:VwygUrnw9jn88c4u8GD3rZQbqrP/tgas88tPUbBxQrk=";
+        let purl = PackageUrl::from_go_sum_line(line).unwrap();
+        assert_eq!(
+            purl.go_lowercase_module_path().as_deref(),
+            Some("golang.org/x/sys")
+        );
+        let version = purl.go_pseudo_version().unwrap();
+        assert_eq!(version.commit(), "22da62e12c0c");
+        assert!(PackageUrl::from_go_sum_line("golang.org/x/sys v0.0.0").is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let purl = PackageUrl::parse("pkg:golang/github.com/Gorilla/context@v1.1.1").unwrap();
+        assert_eq!(purl.namespace(), Some("github.com/gorilla"));
+        let registry = crate::TypeRegistry::builtin();
+        assert_eq!(
+            registry.native_coordinates(&purl).as_deref(),
+            Some("github.com/gorilla/context@v1.1.1")
+        );
+
+        let purl = PackageUrl::from_canonical_parts(
+            "golang",
+            Some("github.com/Gorilla"),
+            "context",
+            None,
+            &[],
+            None,
+        );
+        let err = registry.validate(&purl).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::InvalidNamespaceComponent("Gorilla".into())
+        );
+    }
+}
@@ -0,0 +1,238 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+use crate::errors::Error;
+use crate::errors::ErrorKind;
+use crate::errors::Result;
+use crate::purl::PackageUrl;
+use crate::purl_type::PurlType;
+use crate::rules::TypeRules;
+
+/// The extension of an artifact without a `type` qualifier.
+const DEFAULT_TYPE: &str = "jar";
+
+/// The rules of the `maven` type.
+pub(crate) struct MavenRules;
+
+impl TypeRules for MavenRules {
+    normalize_as_defined!(PurlType::Maven);
+
+    fn native_coordinates(&self, purl: &PackageUrl) -> Option<String> {
+        purl.to_maven_coordinates()
+    }
+}
+
+impl<'a> PackageUrl<'a> {
+    /// Create a `maven` Package URL from Maven coordinates.
+    ///
+    /// The coordinates are formatted as `groupId:artifactId:version`, with
+    /// an optional packaging and classifier before the version, i.e.
+    /// `groupId:artifactId[:packaging[:classifier]]:version`. The packaging
+    /// and classifier are recorded in the `type` and `classifier` qualifiers.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// use packageurl::PackageUrl;
+    ///
+    /// let purl = PackageUrl::from_maven_coordinates("org.apache.commons:io:jar:sources:1.3.4")
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     purl.to_string(),
+    ///     "pkg:maven/org.apache.commons/io@1.3.4?classifier=sources&type=jar"
+    /// );
+    /// ```
+    pub fn from_maven_coordinates(coordinates: &'a str) -> Result<Self> {
+        let invalid = || Error::new(ErrorKind::InvalidCoordinates(coordinates.into()));
+
+        let mut parts = coordinates.split(':');
+        let mut next = || parts.next().filter(|part| !part.is_empty());
+        let (group, artifact) = (next(), next());
+        let (packaging, classifier, version) = match (next(), next(), next()) {
+            (Some(version), None, None) => (None, None, version),
+            (Some(packaging), Some(version), None) => (Some(packaging), None, version),
+            (Some(packaging), Some(classifier), Some(version)) => {
+                (Some(packaging), Some(classifier), version)
+            }
+            _ => return Err(invalid()),
+        };
+        let (Some(group), Some(artifact)) = (group, artifact) else {
+            return Err(invalid());
+        };
+        if parts.next().is_some() || group.contains('/') {
+            return Err(invalid());
+        }
+
+        let mut builder = PackageUrl::builder("maven", artifact)
+            .namespace(group)
+            .version(version);
+        if let Some(classifier) = classifier {
+            builder = builder.qualifier("classifier", classifier);
+        }
+        if let Some(packaging) = packaging {
+            builder = builder.qualifier("type", packaging);
+        }
+        builder.build()
+    }
+
+    /// Get the Maven coordinates of a `maven` Package URL.
+    ///
+    /// Returns `None` for other types, or if the namespace or the version
+    /// are missing. A classifier without a `type` qualifier implies the
+    /// default `jar` packaging.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// use packageurl::PackageUrl;
+    ///
+    /// let purl = PackageUrl::parse("pkg:maven/org.apache.commons/io@1.3.4?classifier=sources")
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     purl.to_maven_coordinates().as_deref(),
+    ///     Some("org.apache.commons:io:jar:sources:1.3.4")
+    /// );
+    /// ```
+    pub fn to_maven_coordinates(&self) -> Option<String> {
+        let (group, artifact, version) = self.maven_gav()?;
+        let mut coordinates = String::new();
+        coordinates.push_str(group);
+        coordinates.push(':');
+        coordinates.push_str(artifact);
+        let packaging = self.qualifiers().get("type");
+        match self.qualifiers().get("classifier") {
+            Some(classifier) => {
+                coordinates.push(':');
+                coordinates.push_str(packaging.unwrap_or(DEFAULT_TYPE));
+                coordinates.push(':');
+                coordinates.push_str(classifier);
+            }
+            None => {
+                if let Some(packaging) = packaging {
+                    coordinates.push(':');
+                    coordinates.push_str(packaging);
+                }
+            }
+        }
+        coordinates.push(':');
+        coordinates.push_str(version);
+        Some(coordinates)
+    }
+
+    /// Get the path of the artifact of a `maven` Package URL, relative to
+    /// the root of its repository.
+    ///
+    /// Returns `None` for other types, or if the namespace or the version
+    /// are missing. The `type` qualifier gives the extension of the file,
+    /// `jar` by default.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// use packageurl::PackageUrl;
+    ///
+    /// let purl = PackageUrl::parse("pkg:maven/org.apache.commons/io@1.3.4?classifier=sources")
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     purl.maven_artifact_path().as_deref(),
+    ///     Some("org/apache/commons/io/1.3.4/io-1.3.4-sources.jar")
+    /// );
+    /// ```
+    pub fn maven_artifact_path(&self) -> Option<String> {
+        let (group, artifact, version) = self.maven_gav()?;
+        let mut path = group.replace('.', "/");
+        for part in [artifact, version] {
+            path.push('/');
+            path.push_str(part);
+        }
+        path.push('/');
+        path.push_str(artifact);
+        path.push('-');
+        path.push_str(version);
+        if let Some(classifier) = self.qualifiers().get("classifier") {
+            path.push('-');
+            path.push_str(classifier);
+        }
+        path.push('.');
+        path.push_str(self.qualifiers().get("type").unwrap_or(DEFAULT_TYPE));
+        Some(path)
+    }
+
+    /// Get the group, artifact and version of a `maven` Package URL.
+    fn maven_gav(&self) -> Option<(&str, &str, &str)> {
+        match self.purl_type() {
+            PurlType::Maven => Some((self.namespace()?, self.name(), self.version()?)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use alloc::string::ToString;
+
+    use crate::errors::Component;
+
+    use super::*;
+
+    #[test]
+    fn test_from_maven_coordinates() {
+        let cases = [
+            ("g:a:1.0", "pkg:maven/g/a@1.0"),
+            ("g:a:pom:1.0", "pkg:maven/g/a@1.0?type=pom"),
+            (
+                "g:a:jar:tests:1.0",
+                "pkg:maven/g/a@1.0?classifier=tests&type=jar",
+            ),
+        ];
+        for (coordinates, expected) in cases {
+            let purl = PackageUrl::from_maven_coordinates(coordinates).unwrap();
+            assert_eq!(purl.to_string(), expected);
+            assert_eq!(purl.to_maven_coordinates().as_deref(), Some(coordinates));
+        }
+
+        for invalid in [
+            "g:a",
+            "g::1.0",
+            "g:a:jar::1.0",
+            "g:a:b:c:d:1.0",
+            "g/h:a:1.0",
+        ] {
+            let err = PackageUrl::from_maven_coordinates(invalid).unwrap_err();
+            assert_eq!(err.kind(), &ErrorKind::InvalidCoordinates(invalid.into()));
+        }
+    }
+
+    #[test]
+    fn test_to_maven_coordinates() {
+        let purl = PackageUrl::parse("pkg:maven/g/a").unwrap();
+        assert_eq!(purl.to_maven_coordinates(), None);
+        let purl = PackageUrl::parse("pkg:gradle/g/a@1.0").unwrap();
+        assert_eq!(purl.to_maven_coordinates(), None);
+    }
+
+    #[test]
+    fn test_maven_artifact_path() {
+        let purl = PackageUrl::parse("pkg:maven/org.apache.commons/io@1.3.4?type=pom").unwrap();
+        assert_eq!(
+            purl.maven_artifact_path().as_deref(),
+            Some("org/apache/commons/io/1.3.4/io-1.3.4.pom")
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let err = PackageUrl::parse("pkg:maven/io@1.3.4").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::MissingNamespace("maven".into()));
+        assert_eq!(err.component(), Some(Component::Namespace));
+
+        let purl = PackageUrl::new("maven", "io").unwrap();
+        let registry = crate::TypeRegistry::builtin();
+        let err = registry.validate(&purl).unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::MissingNamespace("maven".into()));
+        assert_eq!(registry.native_coordinates(&purl), None);
+        let err = PackageUrl::builder("maven", "io").build().unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::MissingNamespace("maven".into()));
+    }
+}
@@ -0,0 +1,56 @@
+use alloc::borrow::Cow;
+
+use crate::purl::PackageUrl;
+use crate::purl_type::PurlType;
+use crate::rules::TypeRules;
+use crate::utils;
+
+/// The rules of the `mlflow` type.
+///
+/// Model names are case-insensitive on Databricks, where they are
+/// lowercased, and case-sensitive elsewhere, e.g. on Azure ML.
+pub(crate) struct MlflowRules;
+
+impl TypeRules for MlflowRules {
+    normalize_as_defined!(PurlType::Mlflow);
+
+    fn normalize(&self, purl: &mut PackageUrl<'_>) {
+        let on_databricks = purl
+            .qualifiers()
+            .get("repository_url")
+            .is_some_and(is_databricks);
+        if on_databricks {
+            purl.name = utils::to_lowercase(core::mem::take(&mut purl.name));
+        }
+    }
+}
+
+/// Check whether a repository URL points to a Databricks workspace.
+fn is_databricks(url: &str) -> bool {
+    let host = match url.split_once("://") {
+        Some((_, rest)) => rest,
+        None => url,
+    };
+    let host = host.split(['/', ':']).next().unwrap_or(host);
+    host.ends_with(".azuredatabricks.net") || host.ends_with(".databricks.com")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let purl = PackageUrl::parse(
+            "pkg:mlflow/CreditFraud@3?repository_url=https://adb-5245952564735461.0.azuredatabricks.net/api/2.0/mlflow",
+        )
+        .unwrap();
+        assert_eq!(purl.name(), "creditfraud");
+        let purl = PackageUrl::parse(
+            "pkg:mlflow/CreditFraud@3?repository_url=https://westus2.api.azureml.ms/mlflow/v1.0",
+        )
+        .unwrap();
+        assert_eq!(purl.name(), "CreditFraud");
+    }
+}
@@ -0,0 +1,61 @@
+//! Rules and conversions specific to some package ecosystems.
+
+use super::rules::TypeRules;
+
+/// Implement the normalization hooks of [`TypeRules`] with the built-in
+/// definition of a type, for rules only adding validation or conversions.
+///
+/// Hooks can be selected, e.g. `normalize_as_defined!(ty, normalize_name: name)`,
+/// to implement the others by hand.
+macro_rules! normalize_as_defined {
+    ($ty:expr) => {
+        normalize_as_defined!(
+            $ty,
+            normalize_namespace: namespace,
+            normalize_name: name,
+            normalize_version: version
+        );
+    };
+    ($ty:expr, $($hook:ident: $component:ident),+) => {
+        $(
+            fn $hook<'a>(&self, value: Cow<'a, str>) -> Cow<'a, str> {
+                match $ty.definition() {
+                    Some(definition) => definition.$component().normalize(value),
+                    None => value,
+                }
+            }
+        )+
+    };
+}
+
+mod cpan;
+mod deb;
+mod golang;
+mod maven;
+mod mlflow;
+mod oci;
+mod rpm;
+
+pub use self::golang::GoPseudoVersion;
+
+/// Get the built-in rules of the given lowercase type, if any.
+///
+/// Types without dedicated rules follow their type definition.
+pub(crate) fn rules(ty: &str) -> Option<&'static dyn TypeRules> {
+    match ty {
+        "cpan" => Some(&cpan::CpanRules),
+        "deb" => Some(&deb::DebRules),
+        "docker" => Some(&oci::DockerRules),
+        "golang" => Some(&golang::GolangRules),
+        "maven" => Some(&maven::MavenRules),
+        "mlflow" => Some(&mlflow::MlflowRules),
+        "oci" => Some(&oci::OciRules),
+        "rpm" => Some(&rpm::RpmRules),
+        _ => None,
+    }
+}
+
+/// Get the vendor of a distribution, i.e. its name up to the first '-'.
+fn vendor(distro: &str) -> &str {
+    distro.split('-').next().unwrap_or(distro)
+}
@@ -0,0 +1,336 @@
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+
+use crate::errors::Component;
+use crate::errors::Error;
+use crate::errors::ErrorKind;
+use crate::errors::Result;
+use crate::purl::PackageUrl;
+use crate::purl_type::PurlType;
+use crate::rules::TypeRules;
+use crate::utils;
+
+/// The registry of images referenced without a domain.
+const DOCKER_HUB: &str = "docker.io";
+
+/// The namespace of the official images of Docker Hub.
+const DOCKER_HUB_LIBRARY: &str = "library";
+
+/// The rules of the `oci` type.
+///
+/// Registries treat repository names as lowercase, so the name is lowercased
+/// even though the type definition keeps it as is. Without a
+/// `repository_url` qualifier, images are looked up on Docker Hub.
+pub(crate) struct OciRules;
+
+impl TypeRules for OciRules {
+    normalize_as_defined!(
+        PurlType::Oci,
+        normalize_namespace: namespace,
+        normalize_version: version
+    );
+
+    fn normalize_name<'a>(&self, name: Cow<'a, str>) -> Cow<'a, str> {
+        utils::to_lowercase(name)
+    }
+
+    fn validate(&self, purl: &PackageUrl) -> Result<()> {
+        match purl.version() {
+            Some(version) if !is_digest(version) => {
+                Err(Error::new(ErrorKind::InvalidVersion(version.into()))
+                    .in_component(Component::Version))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn native_coordinates(&self, purl: &PackageUrl) -> Option<String> {
+        purl.to_image_reference()
+    }
+}
+
+/// The rules of the `docker` type.
+pub(crate) struct DockerRules;
+
+impl TypeRules for DockerRules {
+    normalize_as_defined!(PurlType::Docker);
+
+    fn native_coordinates(&self, purl: &PackageUrl) -> Option<String> {
+        purl.to_image_reference()
+    }
+}
+
+/// Check whether a version is a content digest, e.g. `sha256:0123...`.
+fn is_digest(version: &str) -> bool {
+    match version.split_once(':') {
+        Some((algorithm, encoded)) => !algorithm.is_empty() && !encoded.is_empty(),
+        None => false,
+    }
+}
+
+/// Remove the scheme of a repository URL, if any.
+fn strip_scheme(url: &str) -> &str {
+    match url.split_once("://") {
+        Some((_, rest)) => rest,
+        None => url,
+    }
+    .trim_end_matches('/')
+}
+
+impl<'a> PackageUrl<'a> {
+    /// Create an `oci` or `docker` Package URL from a container image
+    /// reference, such as `ghcr.io/org/app:1.2@sha256:abc`.
+    ///
+    /// For the `oci` type, the name is the last component of the image
+    /// path, the version is the digest, and the full repository is recorded
+    /// in the `repository_url` qualifier. For the `docker` type, the
+    /// namespace is the rest of the image path, the version is the digest or
+    /// else the tag, and the registry is recorded in the `repository_url`
+    /// qualifier unless it is Docker Hub. In both cases, a tag alongside a
+    /// digest is recorded in the `tag` qualifier.
+    ///
+    /// References without a registry domain are resolved against Docker
+    /// Hub, where single-component paths name official `library` images.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// use packageurl::{PackageUrl, PurlType};
+    ///
+    /// let reference = "ghcr.io/org/app:1.2@sha256:abc";
+    /// let purl = PackageUrl::from_image_reference(reference, PurlType::Oci).unwrap();
+    /// assert_eq!(
+    ///     purl.to_string(),
+    ///     "pkg:oci/app@sha256%3Aabc?repository_url=ghcr.io/org/app&tag=1.2"
+    /// );
+    /// let purl = PackageUrl::from_image_reference(reference, PurlType::Docker).unwrap();
+    /// assert_eq!(
+    ///     purl.to_string(),
+    ///     "pkg:docker/org/app@sha256%3Aabc?repository_url=ghcr.io&tag=1.2"
+    /// );
+    /// assert_eq!(purl.to_image_reference().as_deref(), Some(reference));
+    /// ```
+    pub fn from_image_reference(reference: &'a str, ty: PurlType<'_>) -> Result<Self> {
+        let invalid = || Error::new(ErrorKind::InvalidCoordinates(reference.into()));
+
+        let (rest, digest) = match reference.split_once('@') {
+            Some((rest, digest)) if is_digest(digest) => (rest, Some(digest)),
+            Some(_) => return Err(invalid()),
+            None => (reference, None),
+        };
+        let (repository, tag) = match rest.rsplit_once(':') {
+            Some((repository, tag)) if !tag.contains('/') => (repository, Some(tag)),
+            _ => (rest, None),
+        };
+        let (domain, path) = match repository.split_once('/') {
+            Some((domain, path)) if domain.contains(['.', ':']) || domain == "localhost" => {
+                (Some(domain), path)
+            }
+            _ => (None, repository),
+        };
+        if path.split('/').any(str::is_empty) || tag == Some("") {
+            return Err(invalid());
+        }
+        let (namespace, name) = match path.rsplit_once('/') {
+            Some((namespace, name)) => (Some(namespace), name),
+            None => (None, path),
+        };
+
+        let on_docker_hub = domain.map_or(true, |domain| domain == DOCKER_HUB);
+        let mut purl = match ty {
+            PurlType::Oci => {
+                let mut purl = PackageUrl::new("oci", name)?;
+                let repository_url = match namespace {
+                    None if on_docker_hub => {
+                        format!("{}/{}/{}", DOCKER_HUB, DOCKER_HUB_LIBRARY, path)
+                    }
+                    _ => format!("{}/{}", domain.unwrap_or(DOCKER_HUB), path),
+                };
+                purl.add_qualifier("repository_url", repository_url)?;
+                purl
+            }
+            PurlType::Docker => {
+                let mut purl = PackageUrl::new("docker", name)?;
+                match namespace {
+                    Some(DOCKER_HUB_LIBRARY) if on_docker_hub => (),
+                    Some(namespace) => {
+                        purl.with_namespace(namespace)?;
+                    }
+                    None => (),
+                }
+                if let Some(domain) = domain.filter(|_| !on_docker_hub) {
+                    purl.add_qualifier("repository_url", domain)?;
+                }
+                purl
+            }
+            other => {
+                return Err(Error::new(ErrorKind::InvalidType(other.as_str().into()))
+                    .in_component(Component::Type))
+            }
+        };
+        match (digest, tag) {
+            (Some(digest), tag) => {
+                purl.with_version(digest)?;
+                if let Some(tag) = tag {
+                    purl.add_qualifier("tag", tag)?;
+                }
+            }
+            (None, Some(tag)) if purl.purl_type() == &PurlType::Docker => {
+                purl.with_version(tag)?;
+            }
+            (None, Some(tag)) => {
+                purl.add_qualifier("tag", tag)?;
+            }
+            (None, None) => (),
+        }
+        Ok(purl)
+    }
+
+    /// Get the container image reference of an `oci` or `docker` Package
+    /// URL.
+    ///
+    /// Without a `repository_url` qualifier, the image is referenced on
+    /// Docker Hub. Returns `None` for other types.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// use packageurl::PackageUrl;
+    ///
+    /// let purl = PackageUrl::parse("pkg:oci/debian@sha256%3A244fd47e07d10?tag=latest").unwrap();
+    /// assert_eq!(
+    ///     purl.to_image_reference().as_deref(),
+    ///     Some("docker.io/library/debian:latest@sha256:244fd47e07d10")
+    /// );
+    /// let purl = PackageUrl::parse("pkg:docker/cassandra@latest").unwrap();
+    /// assert_eq!(purl.to_image_reference().as_deref(), Some("cassandra:latest"));
+    /// ```
+    pub fn to_image_reference(&self) -> Option<String> {
+        let repository_url = self.qualifiers().get("repository_url").map(strip_scheme);
+        let mut reference = match self.purl_type() {
+            PurlType::Oci => match repository_url {
+                Some(repository_url) => String::from(repository_url),
+                None => format!("{}/{}/{}", DOCKER_HUB, DOCKER_HUB_LIBRARY, self.name()),
+            },
+            PurlType::Docker => {
+                let mut reference = String::new();
+                for part in [repository_url, self.namespace()].into_iter().flatten() {
+                    reference.push_str(part);
+                    reference.push('/');
+                }
+                reference.push_str(self.name());
+                reference
+            }
+            _ => return None,
+        };
+        let version = self.version();
+        let (tag, digest) = match self.qualifiers().get("tag") {
+            Some(tag) => (Some(tag), version),
+            None => match version {
+                Some(version) if is_digest(version) => (None, Some(version)),
+                _ => (version, None),
+            },
+        };
+        if let Some(tag) = tag {
+            reference.push(':');
+            reference.push_str(tag);
+        }
+        if let Some(digest) = digest {
+            reference.push('@');
+            reference.push_str(digest);
+        }
+        Some(reference)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_oci() {
+        let cases = [
+            (
+                "ghcr.io/org/app:1.2@sha256:abc",
+                "pkg:oci/app@sha256%3Aabc?repository_url=ghcr.io/org/app&tag=1.2",
+            ),
+            (
+                "localhost:5000/App:1.2",
+                "pkg:oci/app?repository_url=localhost:5000/App&tag=1.2",
+            ),
+            (
+                "debian",
+                "pkg:oci/debian?repository_url=docker.io/library/debian",
+            ),
+        ];
+        for (reference, expected) in cases {
+            let purl = PackageUrl::from_image_reference(reference, PurlType::Oci).unwrap();
+            assert_eq!(purl.to_string(), expected);
+        }
+
+        let purl = PackageUrl::from_image_reference("debian:12", PurlType::Oci).unwrap();
+        assert_eq!(
+            purl.to_image_reference().as_deref(),
+            Some("docker.io/library/debian:12")
+        );
+    }
+
+    #[test]
+    fn test_docker() {
+        let cases = [
+            (
+                "ghcr.io/org/app:1.2@sha256:abc",
+                "pkg:docker/org/app@sha256%3Aabc?repository_url=ghcr.io&tag=1.2",
+            ),
+            ("cassandra:latest", "pkg:docker/cassandra@latest"),
+            ("docker.io/library/debian", "pkg:docker/debian"),
+            (
+                "smartentry/debian@sha256:dc437cc87d10",
+                "pkg:docker/smartentry/debian@sha256%3Adc437cc87d10",
+            ),
+        ];
+        for (reference, expected) in cases {
+            let purl = PackageUrl::from_image_reference(reference, PurlType::Docker).unwrap();
+            assert_eq!(purl.to_string(), expected);
+        }
+
+        let purl = PackageUrl::parse(
+            "pkg:docker/customer/dockerimage@sha256%3A244fd47e07d10?repository_url=https://gcr.io/",
+        )
+        .unwrap();
+        assert_eq!(
+            purl.to_image_reference().as_deref(),
+            Some("gcr.io/customer/dockerimage@sha256:244fd47e07d10")
+        );
+    }
+
+    #[test]
+    fn test_invalid_reference() {
+        for invalid in [
+            "",
+            "ghcr.io/org//app",
+            "app:",
+            "app@latest",
+            "ghcr.io/app@sha256:",
+        ] {
+            let err = PackageUrl::from_image_reference(invalid, PurlType::Oci).unwrap_err();
+            assert_eq!(err.kind(), &ErrorKind::InvalidCoordinates(invalid.into()));
+        }
+        let err = PackageUrl::from_image_reference("debian", PurlType::Npm).unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::InvalidType("npm".into()));
+    }
+
+    #[test]
+    fn test_validate() {
+        let err = PackageUrl::parse("pkg:oci/library/debian").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::ProhibitedNamespace("oci".into()));
+        let err = PackageUrl::parse("pkg:oci/debian@latest").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::InvalidVersion("latest".into()));
+        let purl = PackageUrl::parse("pkg:oci/Debian@sha256%3A244fd47e07d10").unwrap();
+        assert_eq!(purl.name(), "debian");
+    }
+}
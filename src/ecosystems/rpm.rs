@@ -0,0 +1,260 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+use crate::errors::Error;
+use crate::errors::ErrorKind;
+use crate::errors::Result;
+use crate::purl::PackageUrl;
+use crate::purl_type::PurlType;
+use crate::rules::TypeRules;
+
+/// The architectures that a NEVRA can end with, as known to rpm in its
+/// `rpmrc`, plus the source package markers.
+///
+/// Without this list, a NEVRA missing its architecture would be split at the
+/// last '.' of the release, e.g. `el9` in `bash-5.1.8-6.el9`.
+const ARCHITECTURES: &[&str] = &[
+    "aarch64",
+    "alpha",
+    "alphaev5",
+    "alphaev56",
+    "alphaev6",
+    "alphaev67",
+    "alphapca56",
+    "amd64",
+    "armv3l",
+    "armv4b",
+    "armv4l",
+    "armv5tejl",
+    "armv5tel",
+    "armv6hl",
+    "armv6l",
+    "armv7hl",
+    "armv7hnl",
+    "armv7l",
+    "armv8hcnl",
+    "armv8hl",
+    "armv8hnl",
+    "armv8l",
+    "athlon",
+    "em64t",
+    "geode",
+    "i370",
+    "i386",
+    "i486",
+    "i586",
+    "i686",
+    "i786",
+    "ia32e",
+    "ia64",
+    "loongarch64",
+    "m68k",
+    "m68kmint",
+    "mips",
+    "mips64",
+    "mips64el",
+    "mips64r6",
+    "mips64r6el",
+    "mipsel",
+    "mipsr6",
+    "mipsr6el",
+    "noarch",
+    "nosrc",
+    "parisc",
+    "pentium3",
+    "pentium4",
+    "ppc",
+    "ppc32dy4",
+    "ppc64",
+    "ppc64iseries",
+    "ppc64le",
+    "ppc64p7",
+    "ppc64pseries",
+    "ppc8260",
+    "ppc8560",
+    "ppciseries",
+    "ppcpseries",
+    "riscv64",
+    "rs6000",
+    "s390",
+    "s390x",
+    "sh",
+    "sh3",
+    "sh4",
+    "sh4a",
+    "sparc",
+    "sparc64",
+    "sparc64v",
+    "sparcv8",
+    "sparcv9",
+    "sparcv9v",
+    "src",
+    "x86_64",
+    "x86_64_v2",
+    "x86_64_v3",
+    "x86_64_v4",
+    "xtensa",
+];
+
+/// The rules of the `rpm` type.
+pub(crate) struct RpmRules;
+
+impl TypeRules for RpmRules {
+    normalize_as_defined!(PurlType::Rpm);
+
+    fn native_coordinates(&self, purl: &PackageUrl) -> Option<String> {
+        purl.to_rpm_nevra()
+    }
+}
+
+impl<'a> PackageUrl<'a> {
+    /// Create an `rpm` Package URL from the NEVRA of a package.
+    ///
+    /// The NEVRA is formatted as `name-[epoch:]version-release.arch`, as
+    /// listed by `rpm -qa`. The version of the Package URL is made of the
+    /// version and the release, and the epoch and the architecture are
+    /// recorded in the `epoch` and `arch` qualifiers. The default epoch `0`
+    /// is omitted. The architecture must be one known to rpm, such as
+    /// `x86_64`, `armv7l`, `noarch` or `src`.
+    ///
+    /// The distribution is recorded in the `distro` qualifier, and its name,
+    /// up to the first '-', is used as the namespace.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// use packageurl::PackageUrl;
+    ///
+    /// let purl = PackageUrl::from_rpm_nevra("bash-1:5.1.8-6.el9.x86_64", "rhel-9").unwrap();
+    /// assert_eq!(
+    ///     purl.to_string(),
+    ///     "pkg:rpm/rhel/bash@5.1.8-6.el9?arch=x86_64&distro=rhel-9&epoch=1"
+    /// );
+    /// ```
+    pub fn from_rpm_nevra(nevra: &'a str, distro: &'a str) -> Result<Self> {
+        let invalid = || Error::new(ErrorKind::InvalidCoordinates(nevra.into()));
+
+        let (nevr, arch) = nevra
+            .rsplit_once('.')
+            .filter(|(_, arch)| ARCHITECTURES.contains(arch))
+            .ok_or_else(invalid)?;
+        let (nev, release) = nevr
+            .rsplit_once('-')
+            .filter(|(_, release)| !release.contains(':'))
+            .ok_or_else(invalid)?;
+        let (name, _) = nev.rsplit_once('-').ok_or_else(invalid)?;
+        let (epoch, vr) = match nevr[name.len() + 1..].split_once(':') {
+            Some((epoch, vr)) => (Some(epoch), vr),
+            None => (None, &nevr[name.len() + 1..]),
+        };
+        let version = &vr[..vr.len() - release.len() - 1];
+        let is_epoch = |e: &str| !e.is_empty() && e.bytes().all(|b| b.is_ascii_digit());
+        if [name, version, release].contains(&"") || !epoch.map_or(true, is_epoch) {
+            return Err(invalid());
+        }
+
+        let mut builder = PackageUrl::builder("rpm", name)
+            .namespace(super::vendor(distro))
+            .version(vr)
+            .qualifier("arch", arch)
+            .qualifier("distro", distro);
+        if let Some(epoch) = epoch.filter(|e| !e.trim_start_matches('0').is_empty()) {
+            builder = builder.qualifier("epoch", epoch);
+        }
+        builder.build()
+    }
+
+    /// Get the NEVRA of an `rpm` Package URL.
+    ///
+    /// Returns `None` for other types, or if the version is missing. Without
+    /// an `arch` qualifier, only the name, epoch, version and release are
+    /// returned.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// use packageurl::PackageUrl;
+    ///
+    /// let purl = PackageUrl::parse("pkg:rpm/fedora/curl@7.50.3-1.fc25?arch=i386&epoch=2")
+    ///     .unwrap();
+    /// assert_eq!(purl.to_rpm_nevra().as_deref(), Some("curl-2:7.50.3-1.fc25.i386"));
+    /// ```
+    pub fn to_rpm_nevra(&self) -> Option<String> {
+        if self.purl_type() != &PurlType::Rpm {
+            return None;
+        }
+        let version = self.version()?;
+        let mut nevra = String::from(self.name());
+        nevra.push('-');
+        if let Some(epoch) = self.qualifiers().get("epoch") {
+            nevra.push_str(epoch);
+            nevra.push(':');
+        }
+        nevra.push_str(version);
+        if let Some(arch) = self.qualifiers().get("arch") {
+            nevra.push('.');
+            nevra.push_str(arch);
+        }
+        Some(nevra)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_from_rpm_nevra() {
+        let purl = PackageUrl::from_rpm_nevra("bash-0:5.1.8-6.el9.x86_64", "rhel-9").unwrap();
+        assert_eq!(
+            purl.to_string(),
+            "pkg:rpm/rhel/bash@5.1.8-6.el9?arch=x86_64&distro=rhel-9"
+        );
+        assert_eq!(
+            purl.to_rpm_nevra().as_deref(),
+            Some("bash-5.1.8-6.el9.x86_64")
+        );
+
+        let nevra = "python3-dateutil-1:2.8.1-7.fc36.noarch";
+        let purl = PackageUrl::from_rpm_nevra(nevra, "fedora-36").unwrap();
+        assert_eq!(purl.name(), "python3-dateutil");
+        assert_eq!(purl.namespace(), Some("fedora"));
+        assert_eq!(purl.qualifiers().get("epoch"), Some("1"));
+        assert_eq!(purl.to_rpm_nevra().as_deref(), Some(nevra));
+
+        for (nevra, arch) in [
+            ("glibc-2.36-9.fc37.armv7l", "armv7l"),
+            ("kernel-6.5.6-300.fc39.ppc64p7", "ppc64p7"),
+            ("zlib-1.2.13-4.el10.x86_64_v2", "x86_64_v2"),
+        ] {
+            let purl = PackageUrl::from_rpm_nevra(nevra, "fedora").unwrap();
+            assert_eq!(purl.qualifiers().get("arch"), Some(arch));
+            assert_eq!(purl.to_rpm_nevra().as_deref(), Some(nevra));
+        }
+
+        for invalid in [
+            "bash",
+            "bash-5.1.8.x86_64",
+            "bash-x:5.1-6.noarch",
+            "-5.1-6.noarch",
+            "bash-5.1.8-6.el9",
+            "bash-5.1.8-6.el9.x86-64",
+        ] {
+            let err = PackageUrl::from_rpm_nevra(invalid, "rhel").unwrap_err();
+            assert_eq!(err.kind(), &ErrorKind::InvalidCoordinates(invalid.into()));
+        }
+    }
+
+    #[test]
+    fn test_to_rpm_nevra() {
+        let purl = PackageUrl::parse("pkg:rpm/fedora/curl@7.50.3-1.fc25").unwrap();
+        assert_eq!(purl.to_rpm_nevra().as_deref(), Some("curl-7.50.3-1.fc25"));
+        let purl = PackageUrl::parse("pkg:rpm/fedora/curl?arch=i386").unwrap();
+        assert_eq!(purl.to_rpm_nevra(), None);
+        let purl = PackageUrl::parse("pkg:deb/debian/curl@7.50.3-1?arch=i386").unwrap();
+        assert_eq!(purl.to_rpm_nevra(), None);
+    }
+}
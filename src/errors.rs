@@ -2,8 +2,17 @@
 //!
 //! [`thiserror`]: https://docs.rs/thiserror/
 
-#[derive(thiserror::Error, Debug, PartialEq, Eq)]
-pub enum Error {
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::ops::Range;
+
+/// The kind of error that occurred while parsing or building a Package URL.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
     #[error("invalid scheme: {0:?}")]
     InvalidScheme(String),
     #[error("invalid type: {0:?}")]
@@ -12,6 +21,12 @@ pub enum Error {
     InvalidKey(String),
     #[error("missing name")]
     MissingName,
+    #[error("invalid name: {0:?}")]
+    InvalidName(String),
+    #[error("missing namespace for type {0:?}")]
+    MissingNamespace(String),
+    #[error("type {0:?} does not allow a namespace")]
+    ProhibitedNamespace(String),
     #[error("invalid namespace component: {0:?}")]
     InvalidNamespaceComponent(String),
     #[error("missing scheme")]
@@ -20,14 +35,142 @@ pub enum Error {
     MissingType,
     #[error("invalid subpath segment: {0:?}")]
     InvalidSubpathSegment(String),
+    #[error("empty qualifier value: {0:?}")]
+    EmptyQualifierValue(String),
+    #[error("missing qualifier: {0:?}")]
+    MissingQualifier(String),
+    #[error("invalid version: {0:?}")]
+    InvalidVersion(String),
+    #[error("invalid coordinates: {0:?}")]
+    InvalidCoordinates(String),
+    #[error("duplicate key: {0:?}")]
+    DuplicateKey(String),
+    #[error("input is longer than {0} bytes")]
+    InputTooLong(usize),
+    #[error("more than {0} qualifiers")]
+    TooManyQualifiers(usize),
     #[error("utf-8 decoding failed")]
-    DecodingError(#[source] std::str::Utf8Error),
+    DecodingError(#[source] core::str::Utf8Error),
+    #[error("multiple errors: {}", join(.0))]
+    Multiple(Vec<Error>),
+}
+
+fn join(errors: &[Error]) -> String {
+    errors
+        .iter()
+        .map(Error::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// A component of a Package URL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Component {
+    Scheme,
+    Type,
+    Namespace,
+    Name,
+    Version,
+    Qualifier,
+    Subpath,
+}
+
+impl Display for Component {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(match self {
+            Component::Scheme => "scheme",
+            Component::Type => "type",
+            Component::Namespace => "namespace",
+            Component::Name => "name",
+            Component::Version => "version",
+            Component::Qualifier => "qualifier",
+            Component::Subpath => "subpath",
+        })
+    }
+}
+
+/// An error that occurred while parsing or building a Package URL.
+///
+/// Besides its [`ErrorKind`], an error records the component it relates to,
+/// and the byte range of the input it was found at when parsing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    kind: ErrorKind,
+    component: Option<Component>,
+    span: Option<Range<usize>>,
+}
+
+impl Error {
+    /// Create a new error of the given kind.
+    pub fn new(kind: ErrorKind) -> Self {
+        Self {
+            kind,
+            component: None,
+            span: None,
+        }
+    }
+
+    /// Get the kind of error.
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    /// Get the component the error relates to, if known.
+    pub fn component(&self) -> Option<Component> {
+        self.component
+    }
+
+    /// Get the byte range of the parsed input the error was found at, if any.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// use packageurl::{Component, PackageUrl};
+    ///
+    /// let err = PackageUrl::parse("pkg:type/name?1st-key=value").unwrap_err();
+    /// assert_eq!(err.component(), Some(Component::Qualifier));
+    /// assert_eq!(err.span(), Some(14..21));
+    /// ```
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    /// Record the component the error relates to.
+    pub(crate) fn in_component(mut self, component: Component) -> Self {
+        self.component = Some(component);
+        self
+    }
+
+    /// Record the component and the input range the error was found at.
+    pub(crate) fn at(mut self, component: Component, span: Range<usize>) -> Self {
+        self.component = Some(component);
+        self.span = Some(span);
+        self
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        self.kind.fmt(f)
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        core::error::Error::source(&self.kind)
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        Error::new(kind)
+    }
 }
 
-impl From<std::str::Utf8Error> for Error {
-    fn from(e: std::str::Utf8Error) -> Self {
-        Error::DecodingError(e)
+impl From<core::str::Utf8Error> for Error {
+    fn from(e: core::str::Utf8Error) -> Self {
+        Error::new(ErrorKind::DecodingError(e))
     }
 }
 
-pub type Result<T> = std::result::Result<T, Error>;
+pub type Result<T> = core::result::Result<T, Error>;
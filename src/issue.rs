@@ -0,0 +1,129 @@
+use alloc::string::String;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::ops::Range;
+
+use super::errors::Component;
+use super::errors::Error;
+
+/// The severity of an [`Issue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Severity {
+    /// The input is valid, but not in canonical form.
+    Warning,
+    /// The input is not a valid Package URL.
+    Error,
+}
+
+/// The kind of a [`Warning`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WarningKind {
+    /// A component is not percent-encoded like in the canonical form.
+    NonCanonicalEncoding(String),
+    /// A case-insensitive component is not lowercase.
+    NotLowercase(String),
+    /// Extra slashes, or empty, local ('.') or parent ('..') segments
+    /// that are ignored when parsing.
+    IgnoredSegment(String),
+    /// A qualifier with an empty value, which is ignored when parsing.
+    EmptyQualifierValue(String),
+    /// A qualifier key that appears more than once.
+    DuplicateKey(String),
+    /// Qualifiers that are not sorted by key.
+    UnsortedQualifiers,
+}
+
+impl Display for WarningKind {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            WarningKind::NonCanonicalEncoding(s) => write!(f, "non-canonical encoding: {:?}", s),
+            WarningKind::NotLowercase(s) => write!(f, "not lowercase: {:?}", s),
+            WarningKind::IgnoredSegment(s) => write!(f, "ignored segment: {:?}", s),
+            WarningKind::EmptyQualifierValue(k) => write!(f, "empty qualifier value: {:?}", k),
+            WarningKind::DuplicateKey(k) => write!(f, "duplicate key: {:?}", k),
+            WarningKind::UnsortedQualifiers => f.write_str("qualifiers are not sorted"),
+        }
+    }
+}
+
+/// A problem that does not prevent a Package URL from being parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    kind: WarningKind,
+    component: Component,
+    span: Range<usize>,
+}
+
+impl Warning {
+    pub(crate) fn new(kind: WarningKind, component: Component, span: Range<usize>) -> Self {
+        Self {
+            kind,
+            component,
+            span,
+        }
+    }
+
+    /// Get the kind of warning.
+    pub fn kind(&self) -> &WarningKind {
+        &self.kind
+    }
+
+    /// Get the component the warning relates to.
+    pub fn component(&self) -> Component {
+        self.component
+    }
+
+    /// Get the byte range of the input the warning was found at.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        self.kind.fmt(f)
+    }
+}
+
+/// A problem found by [`PackageUrl::validate`](crate::PackageUrl::validate).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    Error(Error),
+    Warning(Warning),
+}
+
+impl Issue {
+    /// Get the severity of the issue.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Issue::Error(_) => Severity::Error,
+            Issue::Warning(_) => Severity::Warning,
+        }
+    }
+
+    /// Get the component the issue relates to, if known.
+    pub fn component(&self) -> Option<Component> {
+        match self {
+            Issue::Error(e) => e.component(),
+            Issue::Warning(w) => Some(w.component()),
+        }
+    }
+
+    /// Get the byte range of the input the issue was found at, if known.
+    pub fn span(&self) -> Option<Range<usize>> {
+        match self {
+            Issue::Error(e) => e.span(),
+            Issue::Warning(w) => Some(w.span()),
+        }
+    }
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            Issue::Error(e) => write!(f, "error: {}", e),
+            Issue::Warning(w) => write!(f, "warning: {}", w),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 //! `packageurl` is an implementation of the [Package URL] specification for the [Rust] programming language.
+//! It follows the rules of the 1.0 release of the specification, see [`SpecVersion`].
 //!
 //! [Rust]: http://rust-lang.org/
 //! [Package URL]: https://github.com/package-url/purl-spec
@@ -17,14 +18,14 @@
 //! assert_eq!(purl.namespace(), Some("@angular"));
 //! ```
 //!
-//! Parsing a purl may fail, in which case an error kind from the [`errors`] module
-//! is returned:
+//! Parsing a purl may fail, in which case an [`Error`] is returned, recording
+//! the [`ErrorKind`] as well as the component and input range it was found at:
 //! ```rust
 //! use std::str::FromStr;
 //! use packageurl::PackageUrl;
 //!
 //! let err = PackageUrl::from_str("package@0.1.0").unwrap_err();
-//! assert_eq!(err, packageurl::Error::MissingScheme);
+//! assert_eq!(err.kind(), &packageurl::ErrorKind::MissingScheme);
 //! ```
 //!
 //! The parsed [`PackageUrl`] will have a [`'static`] lifetime, so that the
@@ -33,14 +34,62 @@
 //! [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
 //! [`PackageUrl`]: example_generated/struct.PackageUrl.html
 //! [`'static`]: https://doc.rust-lang.org/reference/items/static-items.html#static-lifetime-elision
+//!
+//!
+//! # Features
+//!
+//! - `std` *(enabled by default)*: allow writing Package URLs to
+//!   `std::io::Write` streams. Without it, the crate is `#![no_std]` and
+//!   only depends on `alloc`.
+//! - `memchr`: use the [`memchr`] crate to look for delimiters.
+//! - `serde`: implement `Serialize` and `Deserialize` for [`PackageUrl`].
+//!
+//! [`memchr`]: https://docs.rs/memchr/
+#![no_std]
 #![doc(issue_tracker_base_url = "https://github.com/althonos/packageurl-rs/issues/")]
 
+extern crate alloc;
+#[cfg(any(test, feature = "std"))]
+extern crate std;
+
+mod builder;
+mod cached;
+mod diagnostic;
+mod ecosystems;
 mod errors;
+mod issue;
+mod options;
 mod parser;
 mod purl;
+mod purl_type;
+mod qualifiers;
+mod registry;
+mod rules;
 mod utils;
 mod validation;
 
+pub use builder::PackageUrlBuilder;
+pub use cached::CachedPackageUrl;
+pub use diagnostic::Diagnostic;
+pub use ecosystems::GoPseudoVersion;
+pub use errors::Component;
 pub use errors::Error;
+pub use errors::ErrorKind;
 pub use errors::Result;
+pub use issue::Issue;
+pub use issue::Severity;
+pub use issue::Warning;
+pub use issue::WarningKind;
+pub use options::ParseMode;
+pub use options::ParseOptions;
+pub use options::SpecVersion;
 pub use purl::PackageUrl;
+pub use purl_type::PurlType;
+pub use qualifiers::Qualifiers;
+pub use registry::ComponentDefinition;
+pub use registry::Normalization;
+pub use registry::QualifierDefinition;
+pub use registry::Requirement;
+pub use registry::TypeDefinition;
+pub use registry::TypeRegistry;
+pub use rules::TypeRules;
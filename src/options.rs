@@ -0,0 +1,324 @@
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::hash::Hash;
+use core::hash::Hasher;
+
+use super::registry::TypeRegistry;
+
+/// How forgiving the parser is with inputs that are not in canonical form.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ParseMode {
+    /// Reject anything the specification does not allow: qualifiers with an
+    /// empty value, duplicate qualifier keys, and empty, local ('.') or
+    /// parent ('..') segments in the namespace and the subpath.
+    Strict,
+    /// Skip qualifiers with an empty value and ignored segments. Duplicate
+    /// qualifier keys are rejected, unless following [`SpecVersion::Legacy`].
+    /// This is how [`FromStr`] and [`PackageUrl::parse`] behave.
+    ///
+    /// [`FromStr`]: core::str::FromStr
+    /// [`PackageUrl::parse`]: crate::PackageUrl::parse
+    #[default]
+    Standard,
+    /// Like [`ParseMode::Standard`], but also repair common mistakes:
+    /// surrounding whitespace, an uppercase or missing scheme, an unencoded
+    /// '@' starting a namespace (such as an npm scope), percent-encoded
+    /// octets that are not valid UTF-8, and duplicate qualifier keys, keeping
+    /// the last value.
+    Lenient,
+}
+
+/// The version of the Package URL specification followed when parsing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SpecVersion {
+    /// The rules in use before the 1.0 release of the specification, where
+    /// a duplicate qualifier key overrides the previous value.
+    Legacy,
+    /// The rules of the 1.0 release of the specification, where duplicate
+    /// qualifier keys are invalid.
+    #[default]
+    V1,
+}
+
+/// Options controlling how a Package URL is parsed.
+///
+/// # Example
+/// ```rust
+/// # extern crate packageurl;
+/// use packageurl::{ErrorKind, PackageUrl, ParseOptions};
+///
+/// let options = ParseOptions::lenient();
+/// let purl = PackageUrl::parse_with(" PKG:npm/@angular/core ", &options).unwrap();
+/// assert_eq!(purl.to_string(), "pkg:npm/%40angular/core");
+///
+/// let options = ParseOptions::strict().max_qualifiers(1);
+/// let err = PackageUrl::parse_with("pkg:type/name?a=1&b=2", &options).unwrap_err();
+/// assert_eq!(err.kind(), &ErrorKind::TooManyQualifiers(1));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ParseOptions<'r> {
+    mode: ParseMode,
+    spec_version: SpecVersion,
+    max_length: Option<usize>,
+    max_qualifiers: Option<usize>,
+    registry: RegistryRef<'r>,
+}
+
+impl<'r> ParseOptions<'r> {
+    /// Create the default options, which parse like [`PackageUrl::parse`].
+    ///
+    /// [`PackageUrl::parse`]: crate::PackageUrl::parse
+    pub const fn new() -> Self {
+        Self {
+            mode: ParseMode::Standard,
+            spec_version: SpecVersion::V1,
+            max_length: None,
+            max_qualifiers: None,
+            registry: RegistryRef(None),
+        }
+    }
+
+    /// Create options rejecting inputs that are not strictly valid.
+    pub const fn strict() -> Self {
+        Self::new().mode(ParseMode::Strict)
+    }
+
+    /// Create options repairing common mistakes in the input.
+    pub const fn lenient() -> Self {
+        Self::new().mode(ParseMode::Lenient)
+    }
+
+    /// Set the parsing mode.
+    pub const fn mode(mut self, mode: ParseMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set the version of the specification to follow.
+    pub const fn spec_version(mut self, version: SpecVersion) -> Self {
+        self.spec_version = version;
+        self
+    }
+
+    /// Reject inputs longer than the given number of bytes.
+    pub const fn max_length(mut self, max: usize) -> Self {
+        self.max_length = Some(max);
+        self
+    }
+
+    /// Reject inputs with more than the given number of qualifiers.
+    pub const fn max_qualifiers(mut self, max: usize) -> Self {
+        self.max_qualifiers = Some(max);
+        self
+    }
+
+    /// Apply the type rules of the given registry instead of the built-in one.
+    pub const fn registry(mut self, registry: &'r TypeRegistry) -> Self {
+        self.registry = RegistryRef(Some(registry));
+        self
+    }
+
+    /// Get the parsing mode.
+    pub const fn get_mode(&self) -> ParseMode {
+        self.mode
+    }
+
+    /// Get the version of the specification to follow.
+    pub const fn get_spec_version(&self) -> SpecVersion {
+        self.spec_version
+    }
+
+    /// Get the maximum length of the input, if limited.
+    pub const fn get_max_length(&self) -> Option<usize> {
+        self.max_length
+    }
+
+    /// Get the maximum number of qualifiers, if limited.
+    pub const fn get_max_qualifiers(&self) -> Option<usize> {
+        self.max_qualifiers
+    }
+
+    /// Get the registry of the type rules to apply.
+    pub fn get_registry(&self) -> &'r TypeRegistry {
+        match self.registry.0 {
+            Some(registry) => registry,
+            None => TypeRegistry::builtin(),
+        }
+    }
+
+    pub(crate) fn is_strict(&self) -> bool {
+        self.mode == ParseMode::Strict
+    }
+
+    pub(crate) fn is_lenient(&self) -> bool {
+        self.mode == ParseMode::Lenient
+    }
+
+    /// Check whether duplicate qualifier keys are rejected.
+    pub(crate) fn rejects_duplicate_keys(&self) -> bool {
+        match self.mode {
+            ParseMode::Strict => true,
+            ParseMode::Standard => self.spec_version >= SpecVersion::V1,
+            ParseMode::Lenient => false,
+        }
+    }
+}
+
+/// A reference to a registry, compared by address.
+#[derive(Clone, Copy, Default)]
+struct RegistryRef<'r>(Option<&'r TypeRegistry>);
+
+impl RegistryRef<'_> {
+    fn address(&self) -> Option<*const TypeRegistry> {
+        self.0.map(|r| r as *const TypeRegistry)
+    }
+}
+
+impl Debug for RegistryRef<'_> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self.0 {
+            Some(registry) => registry.fmt(f),
+            None => f.write_str("builtin"),
+        }
+    }
+}
+
+impl PartialEq for RegistryRef<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.address() == other.address()
+    }
+}
+
+impl Eq for RegistryRef<'_> {}
+
+impl Hash for RegistryRef<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.address().hash(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use alloc::string::String;
+    use alloc::string::ToString;
+
+    use crate::ErrorKind;
+    use crate::PackageUrl;
+
+    use super::*;
+
+    fn parse(input: &str, options: ParseOptions) -> Result<String, ErrorKind> {
+        PackageUrl::parse_with(input, &options)
+            .map(|purl| purl.to_string())
+            .map_err(|e| e.kind().clone())
+    }
+
+    #[test]
+    fn test_strict() {
+        let strict = ParseOptions::strict();
+        assert_eq!(
+            parse("pkg:type/name?a=&b=2", strict),
+            Err(ErrorKind::EmptyQualifierValue("a".into()))
+        );
+        assert_eq!(
+            parse("pkg:type/name?a=1&A=2", strict),
+            Err(ErrorKind::DuplicateKey("A".into()))
+        );
+        assert_eq!(
+            parse("pkg:type/a/./b/name", strict),
+            Err(ErrorKind::InvalidNamespaceComponent(".".into()))
+        );
+        assert_eq!(
+            parse("pkg:type/name#a//b", strict),
+            Err(ErrorKind::InvalidSubpathSegment("".into()))
+        );
+        assert_eq!(
+            parse("pkg:maven/io@1.3.4", strict),
+            Err(ErrorKind::MissingNamespace("maven".into()))
+        );
+        assert_eq!(
+            parse("pkg:cargo/rust-lang/rand@0.7.2", strict),
+            Err(ErrorKind::ProhibitedNamespace("cargo".into()))
+        );
+        assert_eq!(
+            parse("pkg:type/ns/name@1.0?a=1&b=2#sub/path", strict).as_deref(),
+            Ok("pkg:type/ns/name@1.0?a=1&b=2#sub/path")
+        );
+    }
+
+    #[test]
+    fn test_standard() {
+        let standard = ParseOptions::default();
+        assert_eq!(
+            parse("pkg:type/a/./b/name?a=&b=1#a//b", standard).as_deref(),
+            Ok("pkg:type/a/b/name?b=1#a/b")
+        );
+        assert_eq!(
+            parse("pkg:type/name?b=1&B=2", standard),
+            Err(ErrorKind::DuplicateKey("B".into()))
+        );
+        assert_eq!(
+            parse("pkg:cargo/rust-lang/rand@0.7.2", standard),
+            Err(ErrorKind::ProhibitedNamespace("cargo".into()))
+        );
+        let legacy = standard.spec_version(SpecVersion::Legacy);
+        assert_eq!(
+            parse("pkg:type/name?b=1&B=2", legacy).as_deref(),
+            Ok("pkg:type/name?b=2")
+        );
+        assert_eq!(
+            parse("PKG:npm/@angular/core", standard),
+            Err(ErrorKind::InvalidScheme("PKG".into()))
+        );
+    }
+
+    #[test]
+    fn test_lenient() {
+        let lenient = ParseOptions::lenient();
+        assert_eq!(
+            parse("\tPkg:npm/@angular/core@12.3.1 \n", lenient).as_deref(),
+            Ok("pkg:npm/%40angular/core@12.3.1")
+        );
+        assert_eq!(
+            parse("npm/@angular/core", lenient).as_deref(),
+            Ok("pkg:npm/%40angular/core")
+        );
+        assert_eq!(
+            parse("pkg:type/n%FFme", lenient).as_deref(),
+            Ok("pkg:type/n%EF%BF%BDme")
+        );
+        assert_eq!(
+            parse("pkg:type/name?b=1&B=2", lenient).as_deref(),
+            Ok("pkg:type/name?b=2")
+        );
+        assert_eq!(
+            parse("http:type/name", lenient),
+            Err(ErrorKind::InvalidScheme("http".into()))
+        );
+    }
+
+    #[test]
+    fn test_limits() {
+        let options = ParseOptions::new().max_length(16);
+        assert_eq!(
+            parse("pkg:type/name", options).as_deref(),
+            Ok("pkg:type/name")
+        );
+        assert_eq!(
+            parse("pkg:type/name@1.0.0", options),
+            Err(ErrorKind::InputTooLong(16))
+        );
+
+        let options = ParseOptions::new().max_qualifiers(2);
+        assert_eq!(
+            parse("pkg:type/name?a=1&b=2", options).as_deref(),
+            Ok("pkg:type/name?a=1&b=2")
+        );
+        assert_eq!(
+            parse("pkg:type/name?a=1&b=2&c=3", options),
+            Err(ErrorKind::TooManyQualifiers(2))
+        );
+    }
+}
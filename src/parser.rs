@@ -1,138 +1,601 @@
+//! Component parsers, consuming a Package URL from both ends.
+//!
+//! Each parser receives the part of the input that remains to be parsed,
+//! along with its byte `offset` in the original input so that errors can
+//! point at the offending range. Errors are sent to a [`Report`], which
+//! either stops parsing or records them and lets the parsers recover. The
+//! report also carries the [`ParseOptions`] the parsers should follow.
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use super::errors::Component;
 use super::errors::Error;
+use super::errors::ErrorKind;
 use super::errors::Result;
+use super::issue::Issue;
+use super::issue::Warning;
+use super::issue::WarningKind;
+use super::options::ParseOptions;
+use super::purl;
+use super::qualifiers::Qualifiers;
+use super::registry::Requirement;
 use super::utils;
 use super::utils::PercentCodec;
 use super::utils::QuickFind;
 use super::validation;
 
-pub fn parse_scheme(input: &str) -> Result<(&str, String)> {
-    if let Some(i) = input.quickfind(b':') {
-        if &input[..i] == "pkg" {
-            let mut j = i + 1;
-            let mut it = input[i + 1..].chars();
-            while let Some('/') = it.next() {
-                j += 1;
+/// The components of a Package URL, before type-specific normalization.
+pub struct Components<'a> {
+    pub ty: Cow<'a, str>,
+    pub namespace: Option<Cow<'a, str>>,
+    pub name: Cow<'a, str>,
+    pub version: Option<Cow<'a, str>>,
+    pub qualifiers: Qualifiers<'a>,
+    pub subpath: Option<Cow<'a, str>>,
+}
+
+/// A sink for the problems found while parsing.
+///
+/// By default, parsing stops at the first error. When collecting, errors and
+/// warnings are recorded, and the parsers recover by skipping the offending
+/// part of the input.
+#[derive(Debug, Default)]
+pub struct Report<'r> {
+    options: ParseOptions<'r>,
+    issues: Option<Vec<Issue>>,
+}
+
+impl<'r> Report<'r> {
+    /// Create a report stopping at the first error, with the given options.
+    pub fn new(options: ParseOptions<'r>) -> Self {
+        Self {
+            options,
+            issues: None,
+        }
+    }
+
+    /// Create a report recording every issue instead of stopping.
+    pub fn collecting() -> Self {
+        Self {
+            options: ParseOptions::default(),
+            issues: Some(Vec::new()),
+        }
+    }
+
+    /// Get the options the parsers should follow.
+    pub fn options(&self) -> &ParseOptions<'r> {
+        &self.options
+    }
+
+    /// Check whether issues are being recorded.
+    pub fn is_collecting(&self) -> bool {
+        self.issues.is_some()
+    }
+
+    /// Record an error, or return it if parsing should stop.
+    pub fn error(&mut self, error: Error) -> Result<()> {
+        match self.issues {
+            Some(ref mut issues) => {
+                issues.push(Issue::Error(error));
+                Ok(())
             }
+            None => Err(error),
+        }
+    }
 
-            Ok((&input[j..], input[..i].to_string()))
-        } else {
-            Err(Error::InvalidScheme(input[..i].to_string()))
+    /// Record a warning, if issues are being recorded.
+    pub fn warning(&mut self, kind: WarningKind, component: Component, span: Range<usize>) {
+        if let Some(ref mut issues) = self.issues {
+            issues.push(Issue::Warning(Warning::new(kind, component, span)));
         }
+    }
+
+    /// Get the recorded issues.
+    pub fn into_issues(self) -> Vec<Issue> {
+        self.issues.unwrap_or_default()
+    }
+}
+
+/// Parse all the components of a Package URL.
+pub fn parse<'a>(input: &'a str, report: &mut Report) -> Result<Components<'a>> {
+    if let Some(max) = report.options().get_max_length() {
+        if input.len() > max {
+            report.error(Error::new(ErrorKind::InputTooLong(max)))?;
+        }
+    }
+
+    // trailing whitespace is trimmed here, leading whitespace is skipped
+    // along with the scheme so that spans keep pointing into the input
+    let input = if report.options().is_lenient() {
+        input.trim_end()
     } else {
-        Err(Error::MissingScheme)
-    }
-}
-
-pub fn parse_subpath(input: &str) -> Result<(&str, Option<String>)> {
-    if let Some(i) = input.quickrfind(b'#') {
-        let mut subpath = String::with_capacity(i + 1);
-        let mut components = input[i + 1..]
-            .trim_matches('/')
-            .split('/')
-            .filter(|&c| !(c.is_empty() || c == "." || c == ".."));
-        if let Some(c) = components.next() {
-            let decoded = c.decode().decode_utf8()?;
-            if validation::is_subpath_segment_valid(&decoded) {
-                subpath.push_str(&decoded);
-            } else {
-                return Err(Error::InvalidSubpathSegment(decoded.to_string()));
-            }
+        input
+    };
+    let (s, _) = parse_scheme(input, report)?;
+    let offset = input.len() - s.len();
+
+    // find the delimiters of the trailing components in a single sweep
+    let delimiters = Delimiters::find(s);
+    let mut end = s.len();
+    let subpath = match delimiters.hash {
+        Some(i) => {
+            let subpath = parse_subpath(&s[i + 1..end], offset + i + 1, report)?;
+            end = i;
+            subpath
         }
-        for c in components {
-            let decoded = c.decode().decode_utf8()?;
-            if validation::is_subpath_segment_valid(&decoded) {
-                subpath.push('/');
-                subpath.push_str(&decoded);
-            } else {
-                return Err(Error::InvalidSubpathSegment(decoded.to_string()));
-            }
+        None => None,
+    };
+    let qualifiers = match delimiters.question {
+        Some(i) => {
+            let qualifiers = parse_qualifiers(&s[i + 1..end], offset + i + 1, report)?;
+            end = i;
+            qualifiers
         }
-        Ok((&input[..i], Some(subpath)))
+        None => Qualifiers::new(),
+    };
+    let version = match delimiters.at {
+        // when lenient, an '@' followed by a '/' starts a namespace segment
+        Some(i) if !(report.options().is_lenient() && s[i + 1..end].contains('/')) => {
+            let version = parse_version(&s[i + 1..end], offset + i + 1, report)?;
+            end = i;
+            Some(version)
+        }
+        _ => None,
+    };
+
+    let s = &s[..end];
+    let (rem, ty) = parse_type(s, offset, report)?;
+    let offset = offset + s.len() - rem.len();
+    let (s, name) = parse_name(rem, offset, report)?;
+    let (_, namespace) = parse_namespace(s, offset, report)?;
+    let span = match namespace {
+        Some(_) => offset..offset + s.len(),
+        None => offset..offset + rem.len(),
+    };
+    check_namespace(&ty, namespace.is_some(), span, report)?;
+    Ok(Components {
+        ty,
+        namespace,
+        name,
+        version,
+        qualifiers,
+        subpath,
+    })
+}
+
+pub fn parse_scheme<'a>(input: &'a str, report: &mut Report) -> Result<(&'a str, &'a str)> {
+    let lenient = report.options().is_lenient();
+    let start = if lenient {
+        input.len() - input.trim_start().len()
     } else {
-        Ok((input, None))
-    }
-}
-
-pub fn parse_qualifiers(input: &str) -> Result<(&str, Vec<(String, String)>)> {
-    if let Some(i) = input.quickrfind(b'?') {
-        let mut qualifiers = Vec::new();
-        let pairs = input[i + 1..]
-            .split('&')
-            .map(|pair| utils::cut(pair, b'='))
-            .filter(|pair| !pair.1.is_empty());
-        for (key, value) in pairs {
-            if validation::is_qualifier_key_valid(key) {
-                qualifiers.push((
-                    key.to_lowercase(),
-                    value.decode().decode_utf8()?.to_string(),
-                ))
-            } else {
-                return Err(Error::InvalidKey(key.to_string()));
+        0
+    };
+    let s = &input[start..];
+    match s.quickfind(b':') {
+        // when lenient, a ':' after a '/' is not part of a missing scheme
+        Some(i) if !(lenient && s[..i].contains('/')) => {
+            let scheme = &s[..i];
+            let j = s.len() - s[i + 1..].trim_start_matches('/').len();
+            if scheme != "pkg" && !(lenient && scheme.eq_ignore_ascii_case("pkg")) {
+                report.error(
+                    Error::new(ErrorKind::InvalidScheme(scheme.to_string()))
+                        .at(Component::Scheme, start..start + i),
+                )?;
+            } else if j > i + 1 {
+                report.warning(
+                    WarningKind::IgnoredSegment(s[i + 1..j].to_string()),
+                    Component::Scheme,
+                    start + i + 1..start + j,
+                );
             }
+            Ok((&s[j..], scheme))
+        }
+        _ if lenient => Ok((s, "")),
+        _ => {
+            report.error(Error::new(ErrorKind::MissingScheme).at(Component::Scheme, 0..0))?;
+            Ok((input, ""))
         }
-        Ok((&input[..i], qualifiers))
-    } else {
-        Ok((input, Vec::new()))
     }
 }
 
-pub fn parse_version(input: &str) -> Result<(&str, Option<String>)> {
-    if let Some(i) = input.quickrfind(b'@') {
-        Ok((
-            &input[..i],
-            Some(input[i + 1..].decode().decode_utf8()?.into()),
-        ))
-    } else {
-        Ok((input, None))
+pub fn parse_subpath<'a>(
+    input: &'a str,
+    offset: usize,
+    report: &mut Report,
+) -> Result<Option<Cow<'a, str>>> {
+    parse_segments(
+        input,
+        offset,
+        Component::Subpath,
+        validation::is_subpath_segment_valid,
+        ErrorKind::InvalidSubpathSegment,
+        report,
+    )
+}
+
+pub fn parse_qualifiers<'a>(
+    input: &'a str,
+    offset: usize,
+    report: &mut Report,
+) -> Result<Qualifiers<'a>> {
+    let mut qualifiers = Qualifiers::new();
+    let mut previous: Option<Cow<str>> = None;
+    let mut start = offset;
+    let strict = report.options().is_strict();
+    let max = report.options().get_max_qualifiers();
+    for (n, pair) in input.split('&').enumerate() {
+        let (key, value) = utils::cut(pair, b'=');
+        let key_span = start..start + key.len();
+        let value_start = start + key.len() + 1;
+        if let Some(max) = max.filter(|&max| n >= max) {
+            report.error(
+                Error::new(ErrorKind::TooManyQualifiers(max))
+                    .at(Component::Qualifier, start..offset + input.len()),
+            )?;
+            break;
+        } else if value.is_empty() && strict {
+            report.error(
+                Error::new(ErrorKind::EmptyQualifierValue(key.to_string()))
+                    .at(Component::Qualifier, start..start + pair.len()),
+            )?;
+        } else if value.is_empty() {
+            report.warning(
+                WarningKind::EmptyQualifierValue(key.to_string()),
+                Component::Qualifier,
+                start..start + pair.len(),
+            );
+        } else if !validation::is_qualifier_key_valid(key) {
+            report.error(
+                Error::new(ErrorKind::InvalidKey(key.to_string()))
+                    .at(Component::Qualifier, key_span),
+            )?;
+        } else {
+            let span = value_start..value_start + value.len();
+            let value = decode(value, Component::Qualifier, span, report)?;
+            // the key was checked above, and a decoded value is never empty
+            if qualifiers.insert_valid(Cow::Borrowed(key), value).is_some() {
+                if report.options().rejects_duplicate_keys() {
+                    report.error(
+                        Error::new(ErrorKind::DuplicateKey(key.to_string()))
+                            .at(Component::Qualifier, key_span.clone()),
+                    )?;
+                } else {
+                    let kind = WarningKind::DuplicateKey(key.to_string());
+                    report.warning(kind, Component::Qualifier, key_span.clone());
+                }
+            }
+            if report.is_collecting() {
+                let lowercase = utils::to_lowercase(Cow::Borrowed(key));
+                if lowercase != key {
+                    let kind = WarningKind::NotLowercase(key.to_string());
+                    report.warning(kind, Component::Qualifier, key_span);
+                }
+                if previous.as_ref().is_some_and(|p| *p > lowercase) {
+                    let span = offset..offset + input.len();
+                    report.warning(WarningKind::UnsortedQualifiers, Component::Qualifier, span);
+                }
+                previous = Some(lowercase);
+            }
+        }
+        start += pair.len() + 1;
     }
+    Ok(qualifiers)
+}
+
+pub fn parse_version<'a>(
+    input: &'a str,
+    offset: usize,
+    report: &mut Report,
+) -> Result<Cow<'a, str>> {
+    decode(
+        input,
+        Component::Version,
+        offset..offset + input.len(),
+        report,
+    )
 }
 
-pub fn parse_type(input: &str) -> Result<(&str, String)> {
+pub fn parse_type<'a>(
+    input: &'a str,
+    offset: usize,
+    report: &mut Report,
+) -> Result<(&'a str, Cow<'a, str>)> {
     match input.quickfind(b'/') {
-        Some(i) if validation::is_type_valid(&input[..i]) => {
-            Ok((&input[i + 1..], input[..i].to_lowercase()))
+        Some(i) => {
+            let ty = &input[..i];
+            let span = offset..offset + i;
+            if !validation::is_type_valid(ty) {
+                report.error(
+                    Error::new(ErrorKind::InvalidType(ty.to_string())).at(Component::Type, span),
+                )?;
+            } else if ty.chars().any(char::is_uppercase) {
+                report.warning(
+                    WarningKind::NotLowercase(ty.to_string()),
+                    Component::Type,
+                    span,
+                );
+            }
+            Ok((&input[i + 1..], utils::to_lowercase(Cow::Borrowed(ty))))
+        }
+        None => {
+            report.error(
+                Error::new(ErrorKind::MissingType)
+                    .at(Component::Type, offset..offset + input.len()),
+            )?;
+            Ok((input, Cow::Borrowed("")))
         }
-        Some(i) => Err(Error::InvalidType(input[..i].to_string())),
-        None => Err(Error::MissingType),
     }
 }
 
-pub fn parse_name(input: &str) -> Result<(&str, String)> {
-    let (rem, name) = utils::rcut(input.trim_matches('/'), b'/');
+pub fn parse_name<'a>(
+    input: &'a str,
+    offset: usize,
+    report: &mut Report,
+) -> Result<(&'a str, Cow<'a, str>)> {
+    let trimmed = input.trim_end_matches('/');
+    if trimmed.len() < input.len() {
+        report.warning(
+            WarningKind::IgnoredSegment(input[trimmed.len()..].to_string()),
+            Component::Name,
+            offset + trimmed.len()..offset + input.len(),
+        );
+    }
+
+    let start = trimmed.quickrfind(b'/').map(|i| i + 1).unwrap_or(0);
+    let name = &trimmed[start..];
+    let span = offset + start..offset + trimmed.len();
+
+    // the namespace is what precedes the separator, unless it only has slashes
+    let mut rem = &input[..start.saturating_sub(1)];
+    if start > 0 && rem.trim_start_matches('/').is_empty() {
+        report.warning(
+            WarningKind::IgnoredSegment(input[..start].to_string()),
+            Component::Namespace,
+            offset..offset + start,
+        );
+        rem = "";
+    }
+
     if name.is_empty() {
-        Err(Error::MissingName)
+        report.error(Error::new(ErrorKind::MissingName).at(Component::Name, span))?;
+        Ok((rem, Cow::Borrowed("")))
     } else {
-        let canonical_name = name.decode().decode_utf8()?.to_string();
-        Ok((rem, canonical_name))
-    }
-}
-
-pub fn parse_namespace(input: &str) -> Result<(&str, Option<String>)> {
-    if !input.is_empty() {
-        let mut namespace = String::with_capacity(input.len());
-        let mut components = input
-            .trim_matches('/')
-            .split('/')
-            .filter(|&c| !(c.is_empty() || c == "." || c == ".."));
-        if let Some(c) = components.next() {
-            let decoded = c.decode().decode_utf8()?;
-            if validation::is_namespace_component_valid(&decoded) {
-                namespace.push_str(&decoded);
-            } else {
-                return Err(Error::InvalidNamespaceComponent(decoded.to_string()));
+        Ok((rem, decode(name, Component::Name, span, report)?))
+    }
+}
+
+pub fn parse_namespace<'a>(
+    input: &'a str,
+    offset: usize,
+    report: &mut Report,
+) -> Result<(&'a str, Option<Cow<'a, str>>)> {
+    let namespace = parse_segments(
+        input,
+        offset,
+        Component::Namespace,
+        validation::is_namespace_component_valid,
+        ErrorKind::InvalidNamespaceComponent,
+        report,
+    )?;
+    Ok(("", namespace))
+}
+
+/// Check the presence of the namespace against the type definition.
+///
+/// This is checked again with the other type rules once the Package URL is
+/// parsed, but reporting it here gives the span of the namespace.
+fn check_namespace(ty: &str, present: bool, span: Range<usize>, report: &mut Report) -> Result<()> {
+    let Some(definition) = report.options().get_registry().get(ty) else {
+        return Ok(());
+    };
+    let kind = match (definition.namespace().requirement(), present) {
+        (Requirement::Required, false) => ErrorKind::MissingNamespace(ty.to_string()),
+        (Requirement::Prohibited, true) => ErrorKind::ProhibitedNamespace(ty.to_string()),
+        _ => return Ok(()),
+    };
+    report.error(Error::new(kind).at(Component::Namespace, span))
+}
+
+/// The positions of the delimiters starting the trailing components.
+///
+/// The subpath starts after the last '#', the qualifiers after the last '?'
+/// before the subpath, and the version after the last '@' before the
+/// qualifiers. All of them are found by sweeping the input once from the end.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Delimiters {
+    hash: Option<usize>,
+    question: Option<usize>,
+    at: Option<usize>,
+}
+
+impl Delimiters {
+    fn find(input: &str) -> Self {
+        let mut delimiters = Self::default();
+        for i in input.quickrfind3(b'#', b'?', b'@') {
+            match input.as_bytes()[i] {
+                // anything found so far was part of the subpath
+                b'#' if delimiters.hash.is_none() => {
+                    delimiters = Self {
+                        hash: Some(i),
+                        ..Self::default()
+                    };
+                }
+                // any '@' found so far was part of the qualifiers
+                b'?' if delimiters.question.is_none() => {
+                    delimiters.question = Some(i);
+                    delimiters.at = None;
+                }
+                b'@' if delimiters.at.is_none() => {
+                    delimiters.at = Some(i);
+                    if delimiters.hash.is_some() && delimiters.question.is_some() {
+                        break;
+                    }
+                }
+                _ => (),
+            }
+        }
+        delimiters
+    }
+}
+
+/// Percent-decode a component, borrowing from the input when possible.
+///
+/// When collecting issues, invalid UTF-8 sequences are replaced, and a
+/// warning is recorded if the input is not encoded like in canonical form.
+fn decode<'a>(
+    input: &'a str,
+    component: Component,
+    span: Range<usize>,
+    report: &mut Report,
+) -> Result<Cow<'a, str>> {
+    // without percent-encoded octets, the input is already valid UTF-8
+    if !report.is_collecting() && input.quickfind(b'%').is_none() {
+        return Ok(Cow::Borrowed(input));
+    }
+    match input.decode().decode_utf8() {
+        Ok(decoded) => {
+            if report.is_collecting()
+                && decoded.encode(purl::encode_set(component)).to_string() != input
+            {
+                let kind = WarningKind::NonCanonicalEncoding(input.to_string());
+                report.warning(kind, component, span);
             }
+            Ok(decoded)
         }
-        for c in components {
-            let decoded = c.decode().decode_utf8()?;
-            if validation::is_namespace_component_valid(&decoded) {
-                namespace.push('/');
-                namespace.push_str(&decoded);
-            } else {
-                return Err(Error::InvalidNamespaceComponent(decoded.to_string()));
+        Err(e) => {
+            if !report.options().is_lenient() {
+                report.error(Error::from(e).at(component, span))?;
             }
+            Ok(input.decode().decode_utf8_lossy())
         }
-        Ok(("", Some(namespace)))
-    } else {
-        Ok(("", None))
+    }
+}
+
+/// Decode and validate the slash-separated segments of a component.
+///
+/// Empty, local ('.') and parent ('..') segments are skipped, as well as
+/// invalid segments when collecting issues. The input is borrowed if no
+/// segment had to be skipped or decoded.
+fn parse_segments<'a>(
+    input: &'a str,
+    offset: usize,
+    component: Component,
+    is_valid: fn(&str) -> bool,
+    invalid: fn(String) -> ErrorKind,
+    report: &mut Report,
+) -> Result<Option<Cow<'a, str>>> {
+    let trimmed = input.trim_matches('/');
+    let leading = input.len() - input.trim_start_matches('/').len();
+    if report.is_collecting() {
+        let trailing = input.len() - input.trim_end_matches('/').len();
+        if leading > 0 {
+            let slashes = input[..leading].to_string();
+            let span = offset..offset + leading;
+            report.warning(WarningKind::IgnoredSegment(slashes), component, span);
+        }
+        if trailing > 0 && !trimmed.is_empty() {
+            let slashes = input[input.len() - trailing..].to_string();
+            let span = offset + input.len() - trailing..offset + input.len();
+            report.warning(WarningKind::IgnoredSegment(slashes), component, span);
+        }
+    }
+
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+
+    let base = offset + leading;
+    let mut joined: Option<String> = None;
+    let mut start: usize = 0;
+    for c in trimmed.split('/') {
+        // the segments seen so far, in case we need to start allocating
+        let prefix = &trimmed[..start.saturating_sub(1)];
+        let span = base + start..base + start + c.len();
+        start += c.len() + 1;
+        if c.is_empty() || c == "." || c == ".." {
+            if report.options().is_strict() {
+                report.error(Error::new(invalid(c.to_string())).at(component, span.clone()))?;
+            }
+            let kind = WarningKind::IgnoredSegment(c.to_string());
+            report.warning(kind, component, span);
+            joined.get_or_insert_with(|| prefix.to_string());
+            continue;
+        }
+        let decoded = decode(c, component, span.clone(), report)?;
+        if !is_valid(&decoded) {
+            report.error(Error::new(invalid(decoded.into_owned())).at(component, span))?;
+            joined.get_or_insert_with(|| prefix.to_string());
+            continue;
+        }
+        if let Cow::Owned(_) = decoded {
+            joined.get_or_insert_with(|| prefix.to_string());
+        }
+        if let Some(ref mut s) = joined {
+            if !s.is_empty() {
+                s.push('/');
+            }
+            s.push_str(&decoded);
+        }
+    }
+
+    match joined {
+        None => Ok(Some(Cow::Borrowed(trimmed))),
+        Some(s) if s.is_empty() => Ok(None),
+        Some(s) => Ok(Some(Cow::Owned(s))),
+    }
+}
+#[cfg(test)]
+mod tests {
+
+    use crate::PackageUrl;
+
+    use super::*;
+
+    fn error_at(input: &str) -> (Component, Range<usize>) {
+        let err = PackageUrl::parse(input).unwrap_err();
+        (err.component().unwrap(), err.span().unwrap())
+    }
+
+    #[test]
+    fn test_delimiters() {
+        let find = |input: &str| {
+            let d = Delimiters::find(input);
+            (d.at, d.question, d.hash)
+        };
+        assert_eq!(find("t/n@v?k=v#sp"), (Some(3), Some(5), Some(9)));
+        assert_eq!(find("t/n?k=a@b#s@p?"), (None, Some(3), Some(9)));
+        assert_eq!(find("t/%40ns/n@v@w"), (Some(11), None, None));
+        assert_eq!(find("t/n"), (None, None, None));
+    }
+
+    #[test]
+    fn test_error_spans() {
+        assert_eq!(error_at("name@0.1.0"), (Component::Scheme, 0..0));
+        assert_eq!(error_at("http://x/y"), (Component::Scheme, 0..4));
+        assert_eq!(error_at("pkg:/t%20y/name"), (Component::Type, 5..10));
+        assert_eq!(error_at("pkg:name"), (Component::Type, 4..8));
+        assert_eq!(error_at("pkg:type/@1.0"), (Component::Name, 9..9));
+        assert_eq!(error_at("pkg:type/%FF"), (Component::Name, 9..12));
+        assert_eq!(error_at("pkg:type/a/%C3/n"), (Component::Namespace, 11..14));
+        assert_eq!(
+            error_at("pkg:type/name@%FF#sub"),
+            (Component::Version, 14..17)
+        );
+        assert_eq!(
+            error_at("pkg:type/name?a=1&1b=2"),
+            (Component::Qualifier, 18..20)
+        );
+        assert_eq!(
+            error_at("pkg:type/name?a=%FF"),
+            (Component::Qualifier, 16..19)
+        );
+        assert_eq!(
+            error_at("pkg:type/name#/a/%2F"),
+            (Component::Subpath, 17..20)
+        );
     }
 }
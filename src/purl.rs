@@ -1,49 +1,102 @@
-use std::borrow::Cow;
-use std::collections::HashMap;
-use std::fmt::Display;
-use std::fmt::Formatter;
-use std::fmt::Result as FmtResult;
-use std::str::FromStr;
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::hash::Hash;
+use core::hash::Hasher;
+use core::iter::once;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::io;
 
 use percent_encoding::AsciiSet;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use super::builder::PackageUrlBuilder;
+use super::cached::CachedPackageUrl;
+use super::errors::Component;
 use super::errors::Error;
+use super::errors::ErrorKind;
 use super::errors::Result;
+use super::issue::Issue;
+use super::options::ParseOptions;
 use super::parser;
+use super::parser::Report;
+use super::purl_type::PurlType;
+use super::qualifiers::Qualifiers;
+use super::registry::TypeRegistry;
+use super::rules::TypeRules;
+use super::utils;
+use super::utils::CmpWriter;
+use super::utils::HashWriter;
+#[cfg(feature = "std")]
+use super::utils::IoWriter;
+use super::utils::LenWriter;
 use super::utils::PercentCodec;
 use super::validation;
 
-const ENCODE_SET: &AsciiSet = &percent_encoding::CONTROLS
-    .add(b' ')
-    .add(b'"')
-    .add(b'#')
-    .add(b'%')
-    .add(b'<')
-    .add(b'>')
-    .add(b'`')
-    .add(b'?')
-    .add(b'{')
-    .add(b'}')
-    // .add(b'/')
-    // .add(b':')
-    .add(b';')
-    .add(b'=')
-    .add(b'+')
-    .add(b'@')
-    .add(b'\\')
-    .add(b'[')
-    .add(b']')
-    .add(b'^')
-    .add(b'|');
+/// The characters percent-encoded in the scheme and the type: all of them
+/// except the unreserved characters of RFC 3986.
+const COMPONENT_ENCODE_SET: &AsciiSet = &percent_encoding::NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// The characters percent-encoded in a namespace segment, including the '/'
+/// that would start a new segment. The ':' never delimits a component after
+/// the scheme, so it is left as is.
+pub(crate) const NAMESPACE_ENCODE_SET: &AsciiSet = &COMPONENT_ENCODE_SET.remove(b':');
+
+/// The characters percent-encoded in the name, including the '/' that would
+/// end the namespace, and the '@', '?' and '#' that would end the name.
+pub(crate) const NAME_ENCODE_SET: &AsciiSet = &COMPONENT_ENCODE_SET.remove(b':');
+
+/// The characters percent-encoded in the version, including the '@' that
+/// would end the name, and the '?' and '#' that would end the version. The
+/// ':' of an epoch or a digest is encoded too, as in the canonical forms of
+/// the specification test suite.
+pub(crate) const VERSION_ENCODE_SET: &AsciiSet = COMPONENT_ENCODE_SET;
+
+/// The characters percent-encoded in a qualifier value, including the '&'
+/// and '=' separating qualifiers, and the '?' and '#' delimiting them. The
+/// ':' and '/' of URLs are left as is.
+pub(crate) const QUALIFIER_ENCODE_SET: &AsciiSet = &COMPONENT_ENCODE_SET.remove(b':').remove(b'/');
+
+/// The characters percent-encoded in a subpath segment, including the '/'
+/// that would start a new segment, and the '#' that would end the qualifiers.
+pub(crate) const SUBPATH_ENCODE_SET: &AsciiSet = &COMPONENT_ENCODE_SET.remove(b':');
+
+/// Get the characters percent-encoded in a component, or in each of its
+/// segments for the namespace and the subpath.
+pub(crate) fn encode_set(component: Component) -> &'static AsciiSet {
+    match component {
+        Component::Namespace => NAMESPACE_ENCODE_SET,
+        Component::Name => NAME_ENCODE_SET,
+        Component::Version => VERSION_ENCODE_SET,
+        Component::Qualifier => QUALIFIER_ENCODE_SET,
+        Component::Subpath => SUBPATH_ENCODE_SET,
+        Component::Scheme | Component::Type => COMPONENT_ENCODE_SET,
+    }
+}
 
 /// A Package URL.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Equality, ordering and hashing are defined over the canonical string form
+/// produced by the [`Display`] implementation. Since components are validated
+/// when set, and each of them is percent-encoded so that it cannot be mistaken
+/// for a delimiter, the canonical string always parses back to an equal
+/// Package URL.
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PackageUrl<'a> {
     /// The package URL type.
-    pub(crate) ty: Cow<'a, str>,
+    pub(crate) ty: PurlType<'a>,
     /// The optional namespace
     pub(crate) namespace: Option<Cow<'a, str>>,
     /// The package name.
@@ -51,7 +104,7 @@ pub struct PackageUrl<'a> {
     /// The optional package version.
     pub(crate) version: Option<Cow<'a, str>>,
     /// The package qualifiers.
-    pub(crate) qualifiers: HashMap<Cow<'a, str>, Cow<'a, str>>,
+    pub(crate) qualifiers: Qualifiers<'a>,
     /// The package subpath.
     pub(crate) subpath: Option<Cow<'a, str>>,
 }
@@ -66,7 +119,8 @@ impl<'a> PackageUrl<'a> {
     /// cannot contain spaces.
     ///
     /// # Name
-    /// The package name will be canonicalize depending on the type: for instance,
+    /// The package name must not be empty, otherwise an error will be returned.
+    /// It will be canonicalize depending on the type: for instance,
     /// 'bitbucket' packages have a case-insensitive name, so the name will be
     /// lowercased if needed.
     ///
@@ -81,56 +135,428 @@ impl<'a> PackageUrl<'a> {
         T: Into<Cow<'a, str>>,
         N: Into<Cow<'a, str>>,
     {
-        let mut t = ty.into();
-        let mut n = name.into();
-        if validation::is_type_valid(&t) {
+        Self::new_with(ty, name, TypeRegistry::builtin())
+    }
+
+    /// Create a new Package URL, following the type rules of a registry.
+    ///
+    /// This behaves like [`PackageUrl::new`], but normalizes the name with
+    /// the rules registered for the type. The setters only follow the
+    /// built-in definitions: use [`TypeRegistry::validate`] to check the
+    /// Package URL once all of its components are set.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// use std::borrow::Cow;
+    /// use packageurl::{PackageUrl, TypeRegistry, TypeRules};
+    ///
+    /// struct Uppercase;
+    ///
+    /// impl TypeRules for Uppercase {
+    ///     fn normalize_name<'a>(&self, name: Cow<'a, str>) -> Cow<'a, str> {
+    ///         Cow::Owned(name.to_uppercase())
+    ///     }
+    /// }
+    ///
+    /// let mut registry = TypeRegistry::new();
+    /// registry.register("internal", Uppercase).unwrap();
+    /// let purl = PackageUrl::new_with("internal", "tool", &registry).unwrap();
+    /// assert_eq!(purl.to_string(), "pkg:internal/TOOL");
+    /// ```
+    pub fn new_with<T, N>(ty: T, name: N, registry: &TypeRegistry) -> Result<Self>
+    where
+        T: Into<Cow<'a, str>>,
+        N: Into<Cow<'a, str>>,
+    {
+        let t = ty.into();
+        let n = name.into();
+        if !validation::is_type_valid(&t) {
+            Err(Error::new(ErrorKind::InvalidType(t.to_string())).in_component(Component::Type))
+        } else if n.is_empty() {
+            Err(Error::new(ErrorKind::MissingName).in_component(Component::Name))
+        } else {
             // lowercase type if needed
-            if !t.chars().all(|c| c.is_uppercase()) {
-                t = Cow::Owned(t.to_lowercase());
-            }
-            // lowercase name if required by type and needed
-            match t.as_ref() {
-                "bitbucket" | "deb" | "github" | "hex" | "npm" => {
-                    if !n.chars().all(|c| c.is_uppercase()) {
-                        n = Cow::Owned(n.to_lowercase());
-                    }
-                }
-                "pypi" => {
-                    if !n.chars().all(|c| c.is_uppercase()) {
-                        n = Cow::Owned(n.to_lowercase());
-                    }
-                    if n.chars().any(|c| c == '_') {
-                        n = Cow::Owned(n.replace('_', "-"));
-                    }
-                }
-                _ => {}
+            let t = PurlType::from(t);
+            // canonicalize name according to the type
+            let n = normalize_name(registry.rules(t.as_str()), n);
+            Ok(Self::new_unchecked(t, n))
+        }
+    }
+
+    /// Create a builder for a Package URL with the provided type and name.
+    ///
+    /// Unlike the `with_*` methods, the builder validates and normalizes all
+    /// components at once, and reports every invalid component in the
+    /// returned error. See [`PackageUrlBuilder`] for more details.
+    pub fn builder<T, N>(ty: T, name: N) -> PackageUrlBuilder<'a>
+    where
+        T: Into<Cow<'a, str>>,
+        N: Into<Cow<'a, str>>,
+    {
+        PackageUrlBuilder::new(ty, name)
+    }
+
+    /// Parse a Package URL, borrowing from the input where possible.
+    ///
+    /// Components are only allocated if they need to be percent-decoded or
+    /// normalized according to their type, e.g. a lowercased `github` name.
+    /// Use [`FromStr`] instead to obtain a `PackageUrl<'static>`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// let purl = packageurl::PackageUrl::parse("pkg:cargo/packageurl@0.5.0").unwrap();
+    /// assert_eq!(purl.name(), "packageurl");
+    /// assert_eq!(purl.version(), Some("0.5.0"));
+    /// ```
+    pub fn parse(s: &'a str) -> Result<Self> {
+        Self::parse_with(s, &ParseOptions::default())
+    }
+
+    /// Parse a Package URL with the given options.
+    ///
+    /// The options decide whether inputs that are not in canonical form are
+    /// rejected, accepted or repaired, and can limit the size of the input.
+    /// They also select the [`TypeRegistry`] whose rules normalize and
+    /// validate the parsed Package URL. See [`ParseOptions`] for more details.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// use packageurl::{ErrorKind, PackageUrl, ParseOptions};
+    ///
+    /// let input = "pkg:type/name?arch=&os=linux";
+    /// assert!(PackageUrl::parse_with(input, &ParseOptions::default()).is_ok());
+    ///
+    /// let err = PackageUrl::parse_with(input, &ParseOptions::strict()).unwrap_err();
+    /// assert_eq!(err.kind(), &ErrorKind::EmptyQualifierValue("arch".into()));
+    /// ```
+    pub fn parse_with(s: &'a str, options: &ParseOptions<'_>) -> Result<Self> {
+        let components = parser::parse(s, &mut Report::new(*options))?;
+
+        // The parser already validated every component, so only the
+        // type-specific rules are left to apply
+        let registry = options.get_registry();
+        let purl = Self::from_components(components, registry);
+        registry.validate(&purl)?;
+        Ok(purl)
+    }
+
+    /// Check a Package URL, reporting every problem found in the input.
+    ///
+    /// Unlike [`PackageUrl::parse`], this does not stop at the first error:
+    /// invalid parts of the input are skipped and checking continues. Inputs
+    /// that can be parsed but are not in canonical form, for instance with
+    /// unsorted qualifiers or unnecessary percent-encoding, produce warnings.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// use packageurl::PackageUrl;
+    ///
+    /// let issues = PackageUrl::validate("pkg:Type/name?b=2&1a=x&a=%41");
+    /// let messages = issues.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     messages,
+    ///     [
+    ///         "warning: not lowercase: \"Type\"",
+    ///         "warning: qualifiers are not sorted",
+    ///         "error: invalid key: \"1a\"",
+    ///         "warning: non-canonical encoding: \"%41\"",
+    ///     ]
+    /// );
+    /// ```
+    pub fn validate(s: &str) -> Vec<Issue> {
+        let mut report = Report::collecting();
+        let components = match parser::parse(s, &mut report) {
+            Ok(components) => components,
+            Err(e) => unreachable!("errors are collected, got: {}", e),
+        };
+        let mut issues = report.into_issues();
+
+        // Check the type-specific rules like when parsing, unless the
+        // parser already reported the same violation
+        let registry = TypeRegistry::builtin();
+        if let Err(error) = registry.validate(&PackageUrl::from_components(components, registry)) {
+            let reported = issues
+                .iter()
+                .any(|issue| matches!(issue, Issue::Error(e) if e.kind() == error.kind()));
+            if !reported {
+                issues.push(Issue::Error(error));
             }
+        }
+        issues.sort_by_key(|issue| issue.span().map(|span| span.start));
+        issues
+    }
 
-            Ok(Self::new_unchecked(t, n))
-        } else {
-            Err(Error::InvalidType(t.to_string()))
+    /// Create a Package URL from parsed components, normalizing them with the
+    /// rules of the type.
+    pub(crate) fn from_components(
+        components: parser::Components<'a>,
+        registry: &TypeRegistry,
+    ) -> Self {
+        let ty = PurlType::from(components.ty);
+        let rules = registry.rules(ty.as_str());
+        let mut purl = Self {
+            namespace: components
+                .namespace
+                .map(|ns| normalize_namespace(rules, ns)),
+            name: normalize_name(rules, components.name),
+            version: components.version.map(|v| normalize_version(rules, v)),
+            qualifiers: components.qualifiers,
+            subpath: components.subpath,
+            ty,
+        };
+        if let Some(rules) = rules {
+            rules.normalize(&mut purl);
         }
+        purl
     }
 
     /// Create a new Package URL without checking the type.
-    fn new_unchecked<T, N>(ty: T, name: N) -> Self
+    fn new_unchecked<N>(ty: PurlType<'a>, name: N) -> Self
     where
-        T: Into<Cow<'a, str>>,
         N: Into<Cow<'a, str>>,
     {
         Self {
-            ty: ty.into(),
+            ty,
             namespace: None,
             name: name.into(),
             version: None,
-            qualifiers: HashMap::new(),
+            qualifiers: Qualifiers::new(),
             subpath: None,
         }
     }
 
+    /// Create a Package URL from components that are already canonical.
+    ///
+    /// This is used by the `purl!` macro of the `packageurl-macros` crate,
+    /// which validates the components at compile time, and is not part of
+    /// the public API.
+    #[doc(hidden)]
+    pub fn from_canonical_parts(
+        ty: &'a str,
+        namespace: Option<&'a str>,
+        name: &'a str,
+        version: Option<&'a str>,
+        qualifiers: &[(&'a str, &'a str)],
+        subpath: Option<&'a str>,
+    ) -> Self {
+        Self {
+            ty: PurlType::from(ty),
+            namespace: namespace.map(Cow::Borrowed),
+            name: Cow::Borrowed(name),
+            version: version.map(Cow::Borrowed),
+            qualifiers: Qualifiers::from_sorted(qualifiers),
+            subpath: subpath.map(Cow::Borrowed),
+        }
+    }
+
+    /// Convert into a Package URL that owns all of its components.
+    ///
+    /// Components that are already owned are moved without being copied.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// use packageurl::PackageUrl;
+    ///
+    /// let purl: PackageUrl<'static> = {
+    ///     let raw = String::from("pkg:cargo/packageurl@0.5.0");
+    ///     PackageUrl::parse(&raw).unwrap().into_owned()
+    /// };
+    /// assert_eq!(purl.name(), "packageurl");
+    /// ```
+    pub fn into_owned(self) -> PackageUrl<'static> {
+        PackageUrl {
+            ty: self.ty.into_owned(),
+            namespace: self.namespace.map(|ns| Cow::Owned(ns.into_owned())),
+            name: Cow::Owned(self.name.into_owned()),
+            version: self.version.map(|v| Cow::Owned(v.into_owned())),
+            qualifiers: self.qualifiers.into_owned(),
+            subpath: self.subpath.map(|sp| Cow::Owned(sp.into_owned())),
+        }
+    }
+
+    /// Copy all components into a Package URL with a `'static` lifetime.
+    pub fn to_static(&self) -> PackageUrl<'static> {
+        self.as_borrowed().into_owned()
+    }
+
+    /// Get a Package URL borrowing all of its components from `self`.
+    ///
+    /// This never allocates strings, but the qualifier list is copied.
+    pub fn as_borrowed(&self) -> PackageUrl<'_> {
+        PackageUrl {
+            ty: self.ty.as_borrowed(),
+            namespace: self.namespace.as_deref().map(Cow::Borrowed),
+            name: Cow::Borrowed(&self.name),
+            version: self.version.as_deref().map(Cow::Borrowed),
+            qualifiers: self.qualifiers.as_borrowed(),
+            subpath: self.subpath.as_deref().map(Cow::Borrowed),
+        }
+    }
+
+    /// Check whether two Package URLs have the same canonical form.
+    ///
+    /// Package URLs that only differ in non-canonical ways, such as the
+    /// insertion order of qualifiers or qualifiers with an empty value,
+    /// are considered equal.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// let p1 = packageurl::PackageUrl::parse("pkg:/cargo/serde?b=2&a=1").unwrap();
+    /// let p2 = packageurl::PackageUrl::parse("pkg:cargo/serde?a=1&b=2&c=").unwrap();
+    /// assert!(p1.eq_canonical(&p2));
+    /// ```
+    pub fn eq_canonical(&self, other: &PackageUrl<'_>) -> bool {
+        self.cmp_canonical(other) == Ordering::Equal
+    }
+
+    /// Compare the canonical forms of two Package URLs, without allocating.
+    fn cmp_canonical(&self, other: &PackageUrl<'_>) -> Ordering {
+        let mut writer = CmpWriter::new(other.canonical_chunks());
+        let _ = self.write_canonical(&mut writer);
+        writer.finish()
+    }
+
+    /// Write the canonical form of the Package URL.
+    ///
+    /// This is what the [`Display`] implementation writes, but it can be used
+    /// with any [`fmt::Write`] sink. Components are percent-encoded on the fly,
+    /// without allocating.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// let purl = packageurl::PackageUrl::parse("pkg:cargo/packageurl@0.5.0").unwrap();
+    /// let mut csv = String::from("name,purl\n");
+    /// csv.push_str("packageurl,");
+    /// purl.write_canonical(&mut csv).unwrap();
+    /// assert_eq!(csv, "name,purl\npackageurl,pkg:cargo/packageurl@0.5.0");
+    /// ```
+    pub fn write_canonical<W: fmt::Write + ?Sized>(&self, w: &mut W) -> FmtResult {
+        self.canonical_chunks()
+            .try_for_each(|chunk| w.write_str(chunk))
+    }
+
+    /// Iterate over the canonical form of the Package URL, one run of
+    /// percent-encoded bytes or delimiters at a time.
+    fn canonical_chunks(&self) -> impl Iterator<Item = &str> {
+        // Scheme: constant, type: no encoding needed
+        let scheme_and_type = ["pkg:", self.ty.as_str(), "/"];
+
+        // Namespace: percent-encode each component
+        let namespace = self
+            .namespace_segments()
+            .flat_map(|component| component.encode(NAMESPACE_ENCODE_SET).chain(once("/")));
+
+        // Name: percent-encode the name
+        let name = self.name.encode(NAME_ENCODE_SET);
+
+        // Version: percent-encode the version
+        let version = self
+            .version
+            .as_deref()
+            .into_iter()
+            .flat_map(|v| once("@").chain(v.encode(VERSION_ENCODE_SET)));
+
+        // Qualifiers: percent-encode the values, skip empty ones
+        let qualifiers = self
+            .qualifiers
+            .iter()
+            .filter(|(_, v)| !v.is_empty())
+            .enumerate()
+            .flat_map(|(i, (k, v))| {
+                let sep = if i == 0 { "?" } else { "&" };
+                [sep, k, "="]
+                    .into_iter()
+                    .chain(v.encode(QUALIFIER_ENCODE_SET))
+            });
+
+        // Subpath: percent-encode the components
+        let subpath = self.subpath.as_deref().into_iter().flat_map(|sp| {
+            let components = sp
+                .split('/')
+                .filter(|&s| !(s.is_empty() || s == "." || s == ".."))
+                .enumerate()
+                .flat_map(|(i, component)| {
+                    let sep = if i == 0 { "" } else { "/" };
+                    once(sep).chain(component.encode(SUBPATH_ENCODE_SET))
+                });
+            once("#").chain(components)
+        });
+
+        scheme_and_type
+            .into_iter()
+            .chain(namespace)
+            .chain(name)
+            .chain(version)
+            .chain(qualifiers)
+            .chain(subpath)
+    }
+
+    /// Write the canonical form of the Package URL to an I/O stream.
+    ///
+    /// Like [`PackageUrl::write_canonical`], this does not allocate. The
+    /// stream is not flushed, so consider wrapping it in a [`BufWriter`] if
+    /// many small writes are expensive.
+    ///
+    /// [`BufWriter`]: std::io::BufWriter
+    #[cfg(feature = "std")]
+    pub fn write_canonical_io<W: io::Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        let mut writer = IoWriter::new(w);
+        match self.write_canonical(&mut writer) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(writer.into_error()),
+        }
+    }
+
+    /// Get the length of the canonical form of the Package URL, in bytes.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// let purl = packageurl::PackageUrl::parse("pkg:generic/my%20package").unwrap();
+    /// assert_eq!(purl.canonical_len(), purl.to_string().len());
+    /// ```
+    pub fn canonical_len(&self) -> usize {
+        let mut writer = LenWriter::new();
+        let _ = self.write_canonical(&mut writer);
+        writer.finish()
+    }
+
+    /// Format the canonical form once, and keep it along the Package URL.
+    ///
+    /// See [`CachedPackageUrl`] for more details.
+    pub fn into_cached(self) -> CachedPackageUrl<'a> {
+        CachedPackageUrl::new(self)
+    }
+
     /// Get the Package URL type.
     pub fn ty(&self) -> &str {
-        self.ty.as_ref()
+        self.ty.as_str()
+    }
+
+    /// Get the Package URL type, to match on registered types.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// use packageurl::{PackageUrl, PurlType};
+    ///
+    /// let purl = PackageUrl::parse("pkg:npm/%40angular/core@12.3.1").unwrap();
+    /// let registry = match purl.purl_type() {
+    ///     PurlType::Npm => "https://registry.npmjs.org",
+    ///     PurlType::Cargo => "https://crates.io",
+    ///     _ => "unknown",
+    /// };
+    /// assert_eq!(registry, "https://registry.npmjs.org");
+    /// ```
+    pub fn purl_type(&self) -> &PurlType<'a> {
+        &self.ty
     }
 
     /// Get the optional namespace.
@@ -149,32 +575,97 @@ impl<'a> PackageUrl<'a> {
     }
 
     /// Get the package qualifiers
-    pub fn qualifiers(&self) -> &HashMap<Cow<'a, str>, Cow<'a, str>> {
+    pub fn qualifiers(&self) -> &Qualifiers<'a> {
         &self.qualifiers
     }
 
+    /// Get a mutable reference to the package qualifiers
+    pub fn qualifiers_mut(&mut self) -> &mut Qualifiers<'a> {
+        &mut self.qualifiers
+    }
+
     /// Get the optional package subpath.
     pub fn subpath(&self) -> Option<&str> {
         self.subpath.as_ref().map(Cow::as_ref)
     }
 
+    /// Change the type of the package.
+    ///
+    /// The type must be valid, otherwise an error will be returned. The name
+    /// and namespace are canonicalized again according to the new type: for
+    /// instance, changing the type to 'pypi' will lowercase the name and
+    /// replace underscores with dashes. If the Package URL breaks the rules
+    /// of the new type, e.g. with a namespace where it is prohibited, an error
+    /// is returned and the type is left unchanged.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// let mut purl = packageurl::PackageUrl::new("generic", "Django_Rest").unwrap();
+    /// purl.with_type("pypi").unwrap();
+    /// assert_eq!(purl.to_string(), "pkg:pypi/django-rest");
+    /// ```
+    pub fn with_type<T>(&mut self, ty: T) -> Result<&mut Self>
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        let t = ty.into();
+        if !validation::is_type_valid(&t) {
+            return Err(Error::new(ErrorKind::InvalidType(t.into())).in_component(Component::Type));
+        }
+        self.update(|purl| {
+            purl.ty = PurlType::from(t);
+            let rules = builtin_rules(&purl.ty);
+            purl.name = normalize_name(rules, core::mem::take(&mut purl.name));
+            if let Some(ns) = purl.namespace.take() {
+                purl.namespace = Some(normalize_namespace(rules, ns));
+            }
+            if let Some(v) = purl.version.take() {
+                purl.version = Some(normalize_version(rules, v));
+            }
+        })
+    }
+
+    /// Assign a name to the package.
+    ///
+    /// The name must not be empty, otherwise an error will be returned. It
+    /// will be canonicalized depending on the type, like in [`PackageUrl::new`],
+    /// and an error is returned if it breaks the rules of the type.
+    pub fn with_name<N>(&mut self, name: N) -> Result<&mut Self>
+    where
+        N: Into<Cow<'a, str>>,
+    {
+        let n = name.into();
+        if n.is_empty() {
+            return Err(Error::new(ErrorKind::MissingName).in_component(Component::Name));
+        }
+        self.update(|purl| {
+            purl.name = normalize_name(builtin_rules(&purl.ty), n);
+        })
+    }
+
     /// Assign a namespace to the package.
-    pub fn with_namespace<N>(&mut self, namespace: N) -> &mut Self
+    ///
+    /// The namespace is made of segments separated by slashes, which must not
+    /// be empty, local ('.') or parent ('..') segments, otherwise an error
+    /// will be returned. An error is also returned if the type prohibits a
+    /// namespace.
+    pub fn with_namespace<N>(&mut self, namespace: N) -> Result<&mut Self>
     where
         N: Into<Cow<'a, str>>,
     {
-        let mut n = namespace.into();
-        match self.ty.as_ref() {
-            "bitbucket" | "deb" | "github" | "golang" | "hex" | "rpm" => {
-                if n.chars().any(|c| c.is_uppercase()) {
-                    n = Cow::Owned(n.to_lowercase());
-                }
+        let ns = namespace.into();
+        for component in ns.split('/') {
+            if !validation::is_namespace_component_valid(component) {
+                return Err(
+                    Error::new(ErrorKind::InvalidNamespaceComponent(component.into()))
+                        .in_component(Component::Namespace),
+                );
             }
-            _ => {}
         }
-
-        self.namespace = Some(n);
-        self
+        self.update(|purl| {
+            purl.namespace = Some(normalize_namespace(builtin_rules(&purl.ty), ns));
+        })
     }
 
     /// Clear the namespace
@@ -183,13 +674,61 @@ impl<'a> PackageUrl<'a> {
         self
     }
 
+    /// Iterate over the segments of the namespace.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// let purl = packageurl::PackageUrl::parse("pkg:golang/google.golang.org/genproto").unwrap();
+    /// assert_eq!(purl.namespace_segments().collect::<Vec<_>>(), ["google.golang.org"]);
+    /// ```
+    pub fn namespace_segments(&self) -> impl DoubleEndedIterator<Item = &str> {
+        self.namespace
+            .as_deref()
+            .into_iter()
+            .flat_map(|ns| ns.split('/'))
+    }
+
+    /// Append a segment to the namespace.
+    ///
+    /// Segments must not be empty, local ('.') or parent ('..') segments, and
+    /// cannot contain a slash, otherwise an error will be returned. An error
+    /// is also returned if the type prohibits a namespace.
+    pub fn push_namespace_segment<S>(&mut self, segment: S) -> Result<&mut Self>
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        let s = segment.into();
+        if !validation::is_namespace_component_valid(&s) {
+            return Err(Error::new(ErrorKind::InvalidNamespaceComponent(s.into()))
+                .in_component(Component::Namespace));
+        }
+        self.update(|purl| {
+            let s = normalize_namespace(builtin_rules(&purl.ty), s);
+            push_segment(&mut purl.namespace, s);
+        })
+    }
+
+    /// Remove the last segment of the namespace and return it.
+    ///
+    /// The namespace is cleared once its last segment is removed.
+    pub fn pop_namespace_segment(&mut self) -> Option<Cow<'a, str>> {
+        pop_segment(&mut self.namespace)
+    }
+
     /// Assign a version to the package.
-    pub fn with_version<V>(&mut self, version: V) -> &mut Self
+    ///
+    /// The version is canonicalized depending on the type. An error is
+    /// returned if it breaks the rules of the type, e.g. a tag instead of a
+    /// digest for 'oci'.
+    pub fn with_version<V>(&mut self, version: V) -> Result<&mut Self>
     where
         V: Into<Cow<'a, str>>,
     {
-        self.version = Some(version.into());
-        self
+        let v = version.into();
+        self.update(|purl| {
+            purl.version = Some(normalize_version(builtin_rules(&purl.ty), v));
+        })
     }
 
     /// Clear the version
@@ -209,7 +748,10 @@ impl<'a> PackageUrl<'a> {
         let s = subpath.into();
         for component in s.split('/') {
             if !validation::is_subpath_segment_valid(component) {
-                return Err(Error::InvalidSubpathSegment(component.into()));
+                return Err(
+                    Error::new(ErrorKind::InvalidSubpathSegment(component.into()))
+                        .in_component(Component::Subpath),
+                );
             }
         }
         self.subpath = Some(s);
@@ -222,6 +764,38 @@ impl<'a> PackageUrl<'a> {
         self
     }
 
+    /// Iterate over the segments of the subpath.
+    pub fn subpath_segments(&self) -> impl DoubleEndedIterator<Item = &str> {
+        self.subpath
+            .as_deref()
+            .into_iter()
+            .flat_map(|sp| sp.split('/'))
+    }
+
+    /// Append a segment to the subpath.
+    ///
+    /// Segments must not be empty, local ('.') or parent ('..') segments, and
+    /// cannot contain a slash, otherwise an error will be returned.
+    pub fn push_subpath_segment<S>(&mut self, segment: S) -> Result<&mut Self>
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        let s = segment.into();
+        if !validation::is_subpath_segment_valid(&s) {
+            return Err(Error::new(ErrorKind::InvalidSubpathSegment(s.into()))
+                .in_component(Component::Subpath));
+        }
+        push_segment(&mut self.subpath, s);
+        Ok(self)
+    }
+
+    /// Remove the last segment of the subpath and return it.
+    ///
+    /// The subpath is cleared once its last segment is removed.
+    pub fn pop_subpath_segment(&mut self) -> Option<Cow<'a, str>> {
+        pop_segment(&mut self.subpath)
+    }
+
     /// Clear qualifiers
     pub fn clear_qualifiers(&mut self) -> &mut Self {
         self.qualifiers.clear();
@@ -229,134 +803,155 @@ impl<'a> PackageUrl<'a> {
     }
 
     /// Add a qualifier to the package.
+    ///
+    /// The other components are canonicalized again if the type depends on
+    /// the qualifier, and an error is returned if the Package URL breaks the
+    /// rules of the type.
     pub fn add_qualifier<K, V>(&mut self, key: K, value: V) -> Result<&mut Self>
     where
         K: Into<Cow<'a, str>>,
         V: Into<Cow<'a, str>>,
     {
-        let mut k = key.into();
-        if !validation::is_qualifier_key_valid(&k) {
-            Err(Error::InvalidKey(k.into()))
-        } else {
-            if k.chars().any(|c| c.is_uppercase()) {
-                k = Cow::Owned(k.to_lowercase());
-            }
-            self.qualifiers.insert(k, value.into());
-            Ok(self)
+        let mut qualifiers = self.qualifiers.clone();
+        qualifiers.insert(key, value)?;
+        self.update(|purl| purl.qualifiers = qualifiers)
+    }
+
+    /// Apply a change, then normalize and check the Package URL against the
+    /// built-in rules of its type, restoring the previous components if they
+    /// are broken.
+    ///
+    /// Components required by the type may still be missing, since they can
+    /// be set afterwards.
+    fn update<F>(&mut self, change: F) -> Result<&mut Self>
+    where
+        F: FnOnce(&mut Self),
+    {
+        let previous = self.clone();
+        change(self);
+        if let Some(rules) = builtin_rules(&self.ty) {
+            rules.normalize(self);
+        }
+        if let Err(e) = TypeRegistry::builtin().validate_incomplete(self) {
+            *self = previous;
+            return Err(e);
         }
+        Ok(self)
     }
 }
 
-impl FromStr for PackageUrl<'static> {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self> {
-        // Parse all components into strings (since we don't know infer from `s` lifetime)
-        let (s, _) = parser::parse_scheme(s)?;
-        let (s, subpath) = parser::parse_subpath(s)?;
-        let (s, ql) = parser::parse_qualifiers(s)?;
-        let (s, version) = parser::parse_version(s)?;
-        let (s, ty) = parser::parse_type(s)?;
-        let (s, mut name) = parser::parse_name(s)?;
-        let (_, mut namespace) = parser::parse_namespace(s)?;
-
-        // Special rules for some types
-        match ty.as_ref() {
-            "bitbucket" | "github" => {
-                name = name.to_lowercase();
-                namespace = namespace.map(|ns| ns.to_lowercase());
-            }
-            "pypi" => {
-                name = name.replace('_', "-").to_lowercase();
-            }
-            _ => {}
-        };
+/// Canonicalize a package name according to the type rules.
+fn normalize_name<'a>(rules: Option<&dyn TypeRules>, name: Cow<'a, str>) -> Cow<'a, str> {
+    match rules {
+        Some(rules) => rules.normalize_name(name),
+        None => name,
+    }
+}
 
-        let mut purl = Self::new(ty, name)?;
-        if let Some(ns) = namespace {
-            purl.with_namespace(ns);
-        }
-        if let Some(v) = version {
-            purl.with_version(v);
-        }
-        if let Some(sp) = subpath {
-            purl.with_subpath(sp)?;
-        }
-        for (k, v) in ql.into_iter() {
-            purl.add_qualifier(k, v)?;
-        }
+/// Canonicalize a package namespace according to the type rules.
+fn normalize_namespace<'a>(rules: Option<&dyn TypeRules>, namespace: Cow<'a, str>) -> Cow<'a, str> {
+    match rules {
+        Some(rules) => rules.normalize_namespace(namespace),
+        None => namespace,
+    }
+}
 
-        // The obtained package url
-        Ok(purl)
+/// Canonicalize a package version according to the type rules.
+fn normalize_version<'a>(rules: Option<&dyn TypeRules>, version: Cow<'a, str>) -> Cow<'a, str> {
+    match rules {
+        Some(rules) => rules.normalize_version(version),
+        None => version,
     }
 }
 
-impl Display for PackageUrl<'_> {
-    fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        // Scheme: constant
-        f.write_str("pkg:")?;
+/// Get the built-in rules of a type, used by the setters.
+fn builtin_rules(ty: &PurlType) -> Option<&'static dyn TypeRules> {
+    TypeRegistry::builtin().rules(ty.as_str())
+}
 
-        // Type: no encoding needed
-        self.ty.fmt(f).and(f.write_str("/"))?;
+/// Append a segment to a slash-separated path.
+fn push_segment<'a>(path: &mut Option<Cow<'a, str>>, segment: Cow<'a, str>) {
+    *path = Some(match path.take() {
+        None => segment,
+        Some(p) => {
+            let mut p = p.into_owned();
+            p.push('/');
+            p.push_str(&segment);
+            Cow::Owned(p)
+        }
+    });
+}
 
-        // Namespace: percent-encode each component
-        if let Some(ref ns) = self.namespace {
-            for component in ns.split('/').map(|s| s.encode(ENCODE_SET)) {
-                component.fmt(f).and(f.write_str("/"))?;
+/// Remove the last segment of a slash-separated path.
+fn pop_segment<'a>(path: &mut Option<Cow<'a, str>>) -> Option<Cow<'a, str>> {
+    match path.take()? {
+        Cow::Borrowed(p) => {
+            let (rest, last) = utils::rcut(p, b'/');
+            if !rest.is_empty() {
+                *path = Some(Cow::Borrowed(rest));
             }
+            Some(Cow::Borrowed(last))
         }
+        Cow::Owned(mut p) => {
+            if let Some(i) = p.rfind('/') {
+                let last = p.split_off(i + 1);
+                p.pop();
+                *path = Some(Cow::Owned(p));
+                Some(Cow::Owned(last))
+            } else {
+                Some(Cow::Owned(p))
+            }
+        }
+    }
+}
 
-        // Name: percent-encode the name
-        self.name.encode(ENCODE_SET).fmt(f)?;
+impl FromStr for PackageUrl<'static> {
+    type Err = Error;
 
-        // Version: percent-encode the version
-        if let Some(ref v) = self.version {
-            f.write_str("@").and(v.encode(ENCODE_SET).fmt(f))?;
-        }
+    fn from_str(s: &str) -> Result<Self> {
+        PackageUrl::parse(s).map(PackageUrl::into_owned)
+    }
+}
 
-        // Qualifiers: percent-encode the values
-        if !self.qualifiers.is_empty() {
-            f.write_str("?")?;
+impl PartialEq for PackageUrl<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.eq_canonical(other)
+    }
+}
 
-            let mut items = self.qualifiers.iter().collect::<Vec<_>>();
-            items.sort();
+impl Eq for PackageUrl<'_> {}
 
-            let mut iter = items.into_iter();
-            if let Some((k, v)) = iter.next() {
-                k.fmt(f)
-                    .and(f.write_str("="))
-                    .and(v.encode(ENCODE_SET).fmt(f))?;
-            }
-            for (k, v) in iter {
-                f.write_str("&")
-                    .and(k.fmt(f))
-                    .and(f.write_str("="))
-                    .and(v.encode(ENCODE_SET).fmt(f))?;
-            }
-        }
+impl PartialOrd for PackageUrl<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
-        // Subpath: percent-encode the components
-        if let Some(ref sp) = self.subpath {
-            f.write_str("#")?;
-            let mut components = sp
-                .split('/')
-                .filter(|&s| !(s.is_empty() || s == "." || s == ".."));
-            if let Some(component) = components.next() {
-                component.encode(ENCODE_SET).fmt(f)?;
-            }
-            for component in components {
-                f.write_str("/")?;
-                component.encode(ENCODE_SET).fmt(f)?;
-            }
-        }
+impl Ord for PackageUrl<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_canonical(other)
+    }
+}
 
-        Ok(())
+impl Hash for PackageUrl<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut writer = HashWriter::new(state);
+        let _ = self.write_canonical(&mut writer);
+        writer.finish();
+    }
+}
+
+impl Display for PackageUrl<'_> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        self.write_canonical(f)
     }
 }
 
 #[cfg(test)]
 mod tests {
 
+    use alloc::string::String;
+
     use super::*;
 
     #[test]
@@ -367,18 +962,296 @@ mod tests {
         assert_eq!(purl.namespace(), Some("name/space"));
         assert_eq!(purl.name(), "name");
         assert_eq!(purl.version(), Some("version"));
-        assert_eq!(purl.qualifiers().get("k1"), Some(&Cow::Borrowed("v1")));
-        assert_eq!(purl.qualifiers().get("k2"), Some(&Cow::Borrowed("v2")));
+        assert_eq!(purl.qualifiers().get("k1"), Some("v1"));
+        assert_eq!(purl.qualifiers().get("k2"), Some("v2"));
         assert_eq!(purl.subpath(), Some("sub/path"));
     }
 
+    #[test]
+    fn test_parse_borrowed() {
+        let raw_purl = "pkg:type/name/space/name@version?k1=v1#sub/path";
+        let purl = PackageUrl::parse(raw_purl).unwrap();
+        assert!(matches!(purl.ty, PurlType::Other(Cow::Borrowed("type"))));
+        assert!(matches!(purl.namespace, Some(Cow::Borrowed("name/space"))));
+        assert!(matches!(purl.name, Cow::Borrowed("name")));
+        assert!(matches!(purl.version, Some(Cow::Borrowed("version"))));
+        assert!(matches!(purl.subpath, Some(Cow::Borrowed("sub/path"))));
+        assert!(matches!(
+            purl.qualifiers.into_iter().next(),
+            Some((Cow::Borrowed("k1"), Cow::Borrowed("v1")))
+        ));
+    }
+
+    #[test]
+    fn test_parse_owned_when_normalized() {
+        let raw_purl = "pkg:GitHub/Package-URL/purl-SPEC@v%201#./a//b";
+        let purl = PackageUrl::parse(raw_purl).unwrap();
+        assert_eq!(purl.purl_type(), &PurlType::Github);
+        assert_eq!(purl.ty(), "github");
+        assert_eq!(purl.namespace(), Some("package-url"));
+        assert_eq!(purl.name(), "purl-spec");
+        assert_eq!(purl.version(), Some("v 1"));
+        assert_eq!(purl.subpath(), Some("a/b"));
+    }
+
+    #[test]
+    fn test_into_owned() {
+        let raw_purl = String::from("pkg:type/name/space/name@version?k1=v1#sub/path");
+        let borrowed = PackageUrl::parse(&raw_purl).unwrap();
+        let owned = borrowed.as_borrowed().into_owned();
+        drop(raw_purl);
+        assert!(matches!(owned.name, Cow::Owned(_)));
+        assert_eq!(
+            owned.to_string(),
+            "pkg:type/name/space/name@version?k1=v1#sub/path"
+        );
+    }
+
+    #[test]
+    fn test_as_borrowed() {
+        let purl = PackageUrl::from_str("pkg:type/name/space/name@version?k1=v1").unwrap();
+        let borrowed = purl.as_borrowed();
+        assert!(matches!(
+            borrowed.ty,
+            PurlType::Other(Cow::Borrowed("type"))
+        ));
+        assert!(matches!(
+            borrowed.namespace,
+            Some(Cow::Borrowed("name/space"))
+        ));
+        assert!(matches!(
+            borrowed.qualifiers.clone().into_iter().next(),
+            Some((Cow::Borrowed("k1"), Cow::Borrowed("v1")))
+        ));
+        assert_eq!(borrowed, purl);
+        assert_eq!(borrowed.to_static(), purl);
+    }
+
+    #[test]
+    fn test_with_type() {
+        let mut purl = PackageUrl::new("generic", "Django_Rest").unwrap();
+        purl.with_namespace("Encode")
+            .unwrap()
+            .with_version("3.14")
+            .unwrap();
+        purl.with_type("GITHUB").unwrap();
+        assert_eq!(purl.to_string(), "pkg:github/encode/django_rest@3.14");
+        let err = purl.with_type("pypi").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::ProhibitedNamespace("pypi".into()));
+        assert_eq!(purl.to_string(), "pkg:github/encode/django_rest@3.14");
+        assert!(purl.with_type("bad type").is_err());
+        assert_eq!(purl.ty(), "github");
+
+        let mut purl = PackageUrl::new("generic", "Django_Rest").unwrap();
+        purl.with_version("3.14")
+            .unwrap()
+            .with_type("pypi")
+            .unwrap();
+        assert_eq!(purl.to_string(), "pkg:pypi/django-rest@3.14");
+    }
+
+    #[test]
+    fn test_namespace_rules() {
+        let mut purl = PackageUrl::new("maven", "io").unwrap();
+        purl.with_version("2.11.0").unwrap();
+        let err = TypeRegistry::builtin().validate(&purl).unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::MissingNamespace("maven".into()));
+        purl.with_namespace("commons-io").unwrap();
+        assert!(TypeRegistry::builtin().validate(&purl).is_ok());
+
+        let mut purl = PackageUrl::new("oci", "debian").unwrap();
+        let err = purl.with_namespace("library").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::ProhibitedNamespace("oci".into()));
+        assert!(purl.push_namespace_segment("library").is_err());
+        assert_eq!(purl.namespace(), None);
+    }
+
+    #[test]
+    fn test_setter_rules() {
+        let mut purl = PackageUrl::new("deb", "curl").unwrap();
+        let err = purl.with_version(":1").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::InvalidVersion(":1".into()));
+        assert_eq!(purl.version(), None);
+
+        let mut purl = PackageUrl::new("oci", "debian").unwrap();
+        assert!(purl.with_version("latest").is_err());
+        assert_eq!(purl.version(), None);
+
+        let mut purl = PackageUrl::new("cpan", "URI::PackageURL").unwrap();
+        let err = purl.with_name("Foo-Bar").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::InvalidName("Foo-Bar".into()));
+        assert_eq!(purl.name(), "URI::PackageURL");
+
+        let mut purl = PackageUrl::new("mlflow", "CreditFraud").unwrap();
+        purl.add_qualifier(
+            "repository_url",
+            "https://adb-5245952564735461.0.azuredatabricks.net/api/2.0/mlflow",
+        )
+        .unwrap();
+        assert_eq!(purl.name(), "creditfraud");
+        assert_eq!(PackageUrl::parse(&purl.to_string()).unwrap(), purl);
+    }
+
+    #[test]
+    fn test_with_name() {
+        let mut purl = PackageUrl::from_str("pkg:npm/%40angular/core@12.3.1").unwrap();
+        purl.with_name("Animations").unwrap();
+        assert_eq!(purl.to_string(), "pkg:npm/%40angular/animations@12.3.1");
+        assert!(purl.with_name("").is_err());
+        assert_eq!(purl.name(), "animations");
+    }
+
+    #[test]
+    fn test_namespace_segments() {
+        let mut purl = PackageUrl::parse("pkg:github/package-url/purl-spec").unwrap();
+        purl.push_namespace_segment("Subgroup").unwrap();
+        assert_eq!(
+            purl.namespace_segments().collect::<Vec<_>>(),
+            ["package-url", "subgroup"]
+        );
+        assert!(purl.push_namespace_segment("").is_err());
+        assert!(purl.push_namespace_segment("..").is_err());
+        assert!(purl.push_namespace_segment("a/b").is_err());
+        assert_eq!(purl.pop_namespace_segment().as_deref(), Some("subgroup"));
+        assert_eq!(purl.pop_namespace_segment().as_deref(), Some("package-url"));
+        assert_eq!(purl.pop_namespace_segment(), None);
+        assert_eq!(purl.namespace(), None);
+        assert_eq!(purl.namespace_segments().count(), 0);
+    }
+
+    #[test]
+    fn test_subpath_segments() {
+        let mut purl = PackageUrl::parse("pkg:type/name#sub/path").unwrap();
+        assert_eq!(purl.subpath_segments().collect::<Vec<_>>(), ["sub", "path"]);
+        assert!(matches!(
+            purl.pop_subpath_segment(),
+            Some(Cow::Borrowed("path"))
+        ));
+        purl.push_subpath_segment("file.txt").unwrap();
+        assert_eq!(purl.subpath(), Some("sub/file.txt"));
+        assert!(purl.push_subpath_segment(".").is_err());
+        assert_eq!(purl.to_string(), "pkg:type/name#sub/file.txt");
+    }
+
+    #[test]
+    fn test_validate_collects_errors() {
+        let issues = PackageUrl::validate("pkg:type/a/%FF/name?1=a&b=%FF&2=b#sub/%2F/../x");
+        let errors = issues
+            .iter()
+            .filter_map(|issue| match issue {
+                Issue::Error(e) => Some((e.component().unwrap(), e.span().unwrap())),
+                Issue::Warning(_) => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            [
+                (Component::Namespace, 11..14),
+                (Component::Qualifier, 20..21),
+                (Component::Qualifier, 26..29),
+                (Component::Qualifier, 30..31),
+                (Component::Subpath, 38..41),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_canonical() {
+        assert!(
+            PackageUrl::validate("pkg:type/name/space/name@1.0?k1=v1&k2=v2#sub/path").is_empty()
+        );
+        let issues = PackageUrl::validate("pkg://type//name/?k=#/sub/./path/");
+        assert!(issues
+            .iter()
+            .all(|i| i.severity() == crate::Severity::Warning));
+        assert_eq!(issues.len(), 7);
+    }
+
+    #[test]
+    fn test_validate_type_definition() {
+        let issues = PackageUrl::validate("pkg:maven/io@1.3.4");
+        let messages = issues.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        assert_eq!(messages, ["error: missing namespace for type \"maven\""]);
+        assert_eq!(issues[0].span(), Some(10..12));
+    }
+
+    #[test]
+    fn test_validate_type_rules() {
+        for input in [
+            "pkg:oci/b@latest",
+            "pkg:oci/a/b",
+            "pkg:deb/ubuntu/x@:1",
+            "pkg:maven/io@1.3.4",
+        ] {
+            let err = PackageUrl::parse(input).unwrap_err();
+            let errors = PackageUrl::validate(input)
+                .into_iter()
+                .filter_map(|issue| match issue {
+                    Issue::Error(e) => Some(e.kind().clone()),
+                    Issue::Warning(_) => None,
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(errors, [err.kind().clone()], "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_custom_rules() {
+        struct Versioned;
+
+        impl TypeRules for Versioned {
+            fn normalize_version<'a>(&self, version: Cow<'a, str>) -> Cow<'a, str> {
+                match version.strip_prefix('v') {
+                    Some(v) => Cow::Owned(v.into()),
+                    None => version,
+                }
+            }
+
+            fn validate(&self, purl: &PackageUrl) -> Result<()> {
+                match purl.qualifiers().get("repository_url") {
+                    Some(_) => Ok(()),
+                    None => Err(Error::new(ErrorKind::MissingQualifier(
+                        "repository_url".into(),
+                    ))),
+                }
+            }
+        }
+
+        let mut registry = TypeRegistry::new();
+        registry.register("PyPI", Versioned).unwrap();
+        let options = ParseOptions::new().registry(&registry);
+
+        // custom rules replace the built-in definition
+        let input = "pkg:pypi/Django_Rest@v1.0?repository_url=repo.example.com";
+        let purl = PackageUrl::parse_with(input, &options).unwrap();
+        assert_eq!(
+            purl.to_string(),
+            "pkg:pypi/Django_Rest@1.0?repository_url=repo.example.com"
+        );
+        let err = PackageUrl::parse_with("pkg:pypi/django", &options).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::MissingQualifier("repository_url".into())
+        );
+        let purl = PackageUrl::new_with("pypi", "Django_Rest", &registry).unwrap();
+        assert_eq!(purl.name(), "Django_Rest");
+
+        // other types keep their built-in or generic behaviour
+        let purl = PackageUrl::parse_with("pkg:npm/Foo@v1.0", &options).unwrap();
+        assert_eq!(purl.to_string(), "pkg:npm/foo@v1.0");
+        let purl = PackageUrl::parse_with("pkg:acme/Foo", &options).unwrap();
+        assert_eq!(purl.to_string(), "pkg:acme/Foo");
+    }
+
     #[test]
     fn test_to_str() {
         let canonical = "pkg:type/name/space/name@version?k1=v1&k2=v2#sub/path";
         let purl_string = PackageUrl::new("type", "name")
             .unwrap()
             .with_namespace("name/space")
+            .unwrap()
             .with_version("version")
+            .unwrap()
             .with_subpath("sub/path")
             .unwrap()
             .add_qualifier("k1", "v1")
@@ -389,6 +1262,33 @@ mod tests {
         assert_eq!(&purl_string, canonical);
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_write_canonical_io() {
+        let purl = PackageUrl::from_str("pkg:maven/org.apache/commons%20io@1.0?k=v#sub").unwrap();
+        let mut buffer = Vec::new();
+        purl.write_canonical_io(&mut buffer).unwrap();
+        assert_eq!(buffer, purl.to_string().as_bytes());
+
+        let mut full = [0u8; 16];
+        let err = purl
+            .write_canonical_io(&mut full.as_mut_slice())
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+    }
+
+    #[test]
+    fn test_canonical_len() {
+        for s in [
+            "pkg:type/name",
+            "pkg:type/name/space/name@1:0?a=b%26c&d=%C3%A9#sub/path",
+            "pkg:npm/%40angular/core@12.3.1",
+        ] {
+            let purl = PackageUrl::from_str(s).unwrap();
+            assert_eq!(purl.canonical_len(), purl.to_string().len());
+        }
+    }
+
     #[test]
     fn test_percent_encoding_idempotent() {
         let orig = "pkg:brew/openssl%25401.1@1.1.1w";
@@ -407,7 +1307,7 @@ mod tests {
         )
         .unwrap();
         let encoded = purl.to_string();
-        assert_eq!(encoded, "pkg:deb/ubuntu/gnome-calculator@1:41.1-2ubuntu2?vcs_url=git%2Bhttps://salsa.debian.org/gnome-team/gnome-calculator.git%40debian/1%2541.1-2");
+        assert_eq!(encoded, "pkg:deb/ubuntu/gnome-calculator@1%3A41.1-2ubuntu2?vcs_url=git%2Bhttps://salsa.debian.org/gnome-team/gnome-calculator.git%40debian/1%2541.1-2");
     }
 
     #[cfg(feature = "serde")]
@@ -415,7 +1315,9 @@ mod tests {
     fn test_serde() {
         let mut purl = PackageUrl::new("type", "name").unwrap();
         purl.with_namespace("name/space")
+            .unwrap()
             .with_version("version")
+            .unwrap()
             .with_subpath("sub/path")
             .unwrap()
             .add_qualifier("k1", "v1")
@@ -429,6 +1331,56 @@ mod tests {
         assert_eq!(purl, purl2);
     }
 
+    #[test]
+    fn test_canonical_equality() {
+        let p1 = PackageUrl::from_str("pkg:/type/name@1%2bx?k2=v2&k1=v1").unwrap();
+        let mut p2 = PackageUrl::new("type", "name").unwrap();
+        p2.with_version("1+x")
+            .unwrap()
+            .add_qualifier("k1", "v1")
+            .unwrap()
+            .add_qualifier("K2", "v2")
+            .unwrap();
+        assert!(p2.add_qualifier("k3", "").is_err());
+        assert!(p1.eq_canonical(&p2));
+        assert_eq!(p1, p2);
+        assert_eq!(p1.cmp(&p2), Ordering::Equal);
+
+        let set = [p1, p2]
+            .into_iter()
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_canonical_ordering() {
+        let purls = [
+            "pkg:type/name@2",
+            "pkg:type/name@10",
+            "pkg:type/name",
+            "pkg:a/name",
+            "pkg:a+b/name",
+            "pkg:type/name?k=v",
+        ]
+        .iter()
+        .map(|s| PackageUrl::from_str(s).unwrap())
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .map(|p| p.to_string())
+        .collect::<Vec<_>>();
+        assert_eq!(
+            purls,
+            [
+                "pkg:a+b/name",
+                "pkg:a/name",
+                "pkg:type/name",
+                "pkg:type/name?k=v",
+                "pkg:type/name@10",
+                "pkg:type/name@2"
+            ]
+        );
+    }
+
     #[test]
     fn test_plus_sign_in_version() {
         let expected = "pkg:type/name@1%2Bx";
@@ -0,0 +1,360 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::registry::TypeDefinition;
+use super::registry::TypeRegistry;
+use super::utils;
+
+/// The type of a Package URL.
+///
+/// Every type registered in the Package URL specification has its own
+/// variant, so that type-specific logic can use exhaustive matching instead
+/// of comparing strings. Any other type is kept in [`PurlType::Other`].
+///
+/// Converting a string with [`From`] ignores ASCII case, but does not check
+/// that the type is valid: use [`PackageUrl::new`] or [`PackageUrl::with_type`]
+/// for that.
+///
+/// [`PackageUrl::new`]: crate::PackageUrl::new
+/// [`PackageUrl::with_type`]: crate::PackageUrl::with_type
+///
+/// # Example
+/// ```rust
+/// # extern crate packageurl;
+/// use packageurl::{PackageUrl, PurlType};
+///
+/// let purl = PackageUrl::parse("pkg:cargo/packageurl@0.5.0").unwrap();
+/// assert_eq!(purl.purl_type(), &PurlType::Cargo);
+///
+/// let purl = PackageUrl::parse("pkg:acme/widget").unwrap();
+/// assert_eq!(purl.purl_type(), &PurlType::Other("acme".into()));
+/// assert_eq!(PurlType::from("Cargo"), PurlType::Cargo);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PurlType<'a> {
+    /// `alpm`
+    Alpm,
+    /// `apk`
+    Apk,
+    /// `bitbucket`
+    Bitbucket,
+    /// `bitnami`
+    Bitnami,
+    /// `cargo`
+    Cargo,
+    /// `cocoapods`
+    Cocoapods,
+    /// `composer`
+    Composer,
+    /// `conan`
+    Conan,
+    /// `conda`
+    Conda,
+    /// `cpan`
+    Cpan,
+    /// `cran`
+    Cran,
+    /// `deb`
+    Deb,
+    /// `docker`
+    Docker,
+    /// `gem`
+    Gem,
+    /// `generic`
+    Generic,
+    /// `github`
+    Github,
+    /// `golang`
+    Golang,
+    /// `hackage`
+    Hackage,
+    /// `hex`
+    Hex,
+    /// `huggingface`
+    Huggingface,
+    /// `luarocks`
+    Luarocks,
+    /// `maven`
+    Maven,
+    /// `mlflow`
+    Mlflow,
+    /// `npm`
+    Npm,
+    /// `nuget`
+    Nuget,
+    /// `oci`
+    Oci,
+    /// `pub`
+    Pub,
+    /// `pypi`
+    Pypi,
+    /// `qpkg`
+    Qpkg,
+    /// `rpm`
+    Rpm,
+    /// `swid`
+    Swid,
+    /// `swift`
+    Swift,
+    /// A type not registered in the specification, in lowercase.
+    Other(Cow<'a, str>),
+}
+
+impl<'a> PurlType<'a> {
+    /// Get the type as it appears in a Package URL.
+    pub fn as_str(&self) -> &str {
+        match self {
+            PurlType::Alpm => "alpm",
+            PurlType::Apk => "apk",
+            PurlType::Bitbucket => "bitbucket",
+            PurlType::Bitnami => "bitnami",
+            PurlType::Cargo => "cargo",
+            PurlType::Cocoapods => "cocoapods",
+            PurlType::Composer => "composer",
+            PurlType::Conan => "conan",
+            PurlType::Conda => "conda",
+            PurlType::Cpan => "cpan",
+            PurlType::Cran => "cran",
+            PurlType::Deb => "deb",
+            PurlType::Docker => "docker",
+            PurlType::Gem => "gem",
+            PurlType::Generic => "generic",
+            PurlType::Github => "github",
+            PurlType::Golang => "golang",
+            PurlType::Hackage => "hackage",
+            PurlType::Hex => "hex",
+            PurlType::Huggingface => "huggingface",
+            PurlType::Luarocks => "luarocks",
+            PurlType::Maven => "maven",
+            PurlType::Mlflow => "mlflow",
+            PurlType::Npm => "npm",
+            PurlType::Nuget => "nuget",
+            PurlType::Oci => "oci",
+            PurlType::Pub => "pub",
+            PurlType::Pypi => "pypi",
+            PurlType::Qpkg => "qpkg",
+            PurlType::Rpm => "rpm",
+            PurlType::Swid => "swid",
+            PurlType::Swift => "swift",
+            PurlType::Other(ty) => ty,
+        }
+    }
+
+    /// Check whether the type is registered in the specification.
+    pub fn is_known(&self) -> bool {
+        !matches!(self, PurlType::Other(_))
+    }
+
+    /// Get the definition of the type in the built-in [`TypeRegistry`], if any.
+    pub fn definition(&self) -> Option<&'static TypeDefinition> {
+        TypeRegistry::builtin().get(self.as_str())
+    }
+
+    /// Convert into a type that owns its string, if any.
+    pub fn into_owned(self) -> PurlType<'static> {
+        match self {
+            PurlType::Other(ty) => PurlType::Other(Cow::Owned(ty.into_owned())),
+            PurlType::Alpm => PurlType::Alpm,
+            PurlType::Apk => PurlType::Apk,
+            PurlType::Bitbucket => PurlType::Bitbucket,
+            PurlType::Bitnami => PurlType::Bitnami,
+            PurlType::Cargo => PurlType::Cargo,
+            PurlType::Cocoapods => PurlType::Cocoapods,
+            PurlType::Composer => PurlType::Composer,
+            PurlType::Conan => PurlType::Conan,
+            PurlType::Conda => PurlType::Conda,
+            PurlType::Cpan => PurlType::Cpan,
+            PurlType::Cran => PurlType::Cran,
+            PurlType::Deb => PurlType::Deb,
+            PurlType::Docker => PurlType::Docker,
+            PurlType::Gem => PurlType::Gem,
+            PurlType::Generic => PurlType::Generic,
+            PurlType::Github => PurlType::Github,
+            PurlType::Golang => PurlType::Golang,
+            PurlType::Hackage => PurlType::Hackage,
+            PurlType::Hex => PurlType::Hex,
+            PurlType::Huggingface => PurlType::Huggingface,
+            PurlType::Luarocks => PurlType::Luarocks,
+            PurlType::Maven => PurlType::Maven,
+            PurlType::Mlflow => PurlType::Mlflow,
+            PurlType::Npm => PurlType::Npm,
+            PurlType::Nuget => PurlType::Nuget,
+            PurlType::Oci => PurlType::Oci,
+            PurlType::Pub => PurlType::Pub,
+            PurlType::Pypi => PurlType::Pypi,
+            PurlType::Qpkg => PurlType::Qpkg,
+            PurlType::Rpm => PurlType::Rpm,
+            PurlType::Swid => PurlType::Swid,
+            PurlType::Swift => PurlType::Swift,
+        }
+    }
+
+    /// Get a type borrowing its string from `self`.
+    pub fn as_borrowed(&self) -> PurlType<'_> {
+        match self {
+            PurlType::Other(ty) => PurlType::Other(Cow::Borrowed(ty)),
+            known => known.clone(),
+        }
+    }
+
+    /// Get the registered type with the given lowercase name, if any.
+    fn known(ty: &str) -> Option<Self> {
+        Some(match ty {
+            "alpm" => PurlType::Alpm,
+            "apk" => PurlType::Apk,
+            "bitbucket" => PurlType::Bitbucket,
+            "bitnami" => PurlType::Bitnami,
+            "cargo" => PurlType::Cargo,
+            "cocoapods" => PurlType::Cocoapods,
+            "composer" => PurlType::Composer,
+            "conan" => PurlType::Conan,
+            "conda" => PurlType::Conda,
+            "cpan" => PurlType::Cpan,
+            "cran" => PurlType::Cran,
+            "deb" => PurlType::Deb,
+            "docker" => PurlType::Docker,
+            "gem" => PurlType::Gem,
+            "generic" => PurlType::Generic,
+            "github" => PurlType::Github,
+            "golang" => PurlType::Golang,
+            "hackage" => PurlType::Hackage,
+            "hex" => PurlType::Hex,
+            "huggingface" => PurlType::Huggingface,
+            "luarocks" => PurlType::Luarocks,
+            "maven" => PurlType::Maven,
+            "mlflow" => PurlType::Mlflow,
+            "npm" => PurlType::Npm,
+            "nuget" => PurlType::Nuget,
+            "oci" => PurlType::Oci,
+            "pub" => PurlType::Pub,
+            "pypi" => PurlType::Pypi,
+            "qpkg" => PurlType::Qpkg,
+            "rpm" => PurlType::Rpm,
+            "swid" => PurlType::Swid,
+            "swift" => PurlType::Swift,
+            _ => return None,
+        })
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for PurlType<'a> {
+    fn from(ty: Cow<'a, str>) -> Self {
+        let ty = utils::to_lowercase(ty);
+        Self::known(&ty).unwrap_or(PurlType::Other(ty))
+    }
+}
+
+impl<'a> From<&'a str> for PurlType<'a> {
+    fn from(ty: &'a str) -> Self {
+        Self::from(Cow::Borrowed(ty))
+    }
+}
+
+impl From<String> for PurlType<'_> {
+    fn from(ty: String) -> Self {
+        Self::from(Cow::Owned(ty))
+    }
+}
+
+impl AsRef<str> for PurlType<'_> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq<str> for PurlType<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for PurlType<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl Display for PurlType<'_> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for PurlType<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for PurlType<'_> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        String::deserialize(deserializer).map(PurlType::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_known() {
+        for ty in [
+            "alpm",
+            "apk",
+            "bitbucket",
+            "bitnami",
+            "cargo",
+            "cocoapods",
+            "composer",
+            "conan",
+            "conda",
+            "cpan",
+            "cran",
+            "deb",
+            "docker",
+            "gem",
+            "generic",
+            "github",
+            "golang",
+            "hackage",
+            "hex",
+            "huggingface",
+            "luarocks",
+            "maven",
+            "mlflow",
+            "npm",
+            "nuget",
+            "oci",
+            "pub",
+            "pypi",
+            "qpkg",
+            "rpm",
+            "swid",
+            "swift",
+        ] {
+            let purl_type = PurlType::from(ty);
+            assert!(purl_type.is_known(), "{} is not known", ty);
+            assert_eq!(purl_type.as_str(), ty);
+            assert!(purl_type.definition().is_some(), "{} has no definition", ty);
+        }
+    }
+
+    #[test]
+    fn test_case_insensitive() {
+        assert_eq!(PurlType::from("GitHub"), PurlType::Github);
+        assert_eq!(PurlType::from("Acme"), PurlType::Other("acme".into()));
+        assert!(matches!(
+            PurlType::from("acme"),
+            PurlType::Other(Cow::Borrowed("acme"))
+        ));
+    }
+}
@@ -0,0 +1,257 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::errors::Component;
+use super::errors::Error;
+use super::errors::ErrorKind;
+use super::errors::Result;
+use super::utils;
+use super::validation;
+
+/// The qualifiers of a Package URL.
+///
+/// Qualifiers are stored sorted by key, so that iteration always follows the
+/// canonical order. Keys are validated and lowercased on insertion, and
+/// lookups are case-insensitive.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Qualifiers<'a> {
+    entries: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+}
+
+impl<'a> Qualifiers<'a> {
+    /// Create an empty set of qualifiers.
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Get the number of qualifiers.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check whether there are no qualifiers.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get the value of the qualifier with the given key, if any.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate packageurl;
+    /// let mut qualifiers = packageurl::Qualifiers::new();
+    /// qualifiers.insert("arch", "x86_64").unwrap();
+    /// assert_eq!(qualifiers.get("ARCH"), Some("x86_64"));
+    /// ```
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.find(key).ok().map(|i| self.entries[i].1.as_ref())
+    }
+
+    /// Check whether a qualifier with the given key exists.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.find(key).is_ok()
+    }
+
+    /// Insert a qualifier, returning the previous value for that key.
+    ///
+    /// The key must be valid, otherwise an error will be returned. It can only
+    /// be composed of ASCII letters and numbers, '.', '-' and '_' (period,
+    /// dash and underscore), and cannot start with a number. The value must
+    /// not be empty, since a qualifier with an empty value is not part of the
+    /// canonical form.
+    pub fn insert<K, V>(&mut self, key: K, value: V) -> Result<Option<Cow<'a, str>>>
+    where
+        K: Into<Cow<'a, str>>,
+        V: Into<Cow<'a, str>>,
+    {
+        let k = key.into();
+        let v = value.into();
+        if !validation::is_qualifier_key_valid(&k) {
+            return Err(
+                Error::new(ErrorKind::InvalidKey(k.into())).in_component(Component::Qualifier)
+            );
+        }
+        if v.is_empty() {
+            return Err(Error::new(ErrorKind::EmptyQualifierValue(k.into()))
+                .in_component(Component::Qualifier));
+        }
+        Ok(self.insert_valid(k, v))
+    }
+
+    /// Insert a qualifier whose key is valid and whose value is not empty.
+    pub(crate) fn insert_valid(
+        &mut self,
+        key: Cow<'a, str>,
+        value: Cow<'a, str>,
+    ) -> Option<Cow<'a, str>> {
+        let key = utils::to_lowercase(key);
+        match self.find(&key) {
+            Ok(i) => Some(core::mem::replace(&mut self.entries[i].1, value)),
+            Err(i) => {
+                self.entries.insert(i, (key, value));
+                None
+            }
+        }
+    }
+
+    /// Remove the qualifier with the given key, returning its value.
+    pub fn remove(&mut self, key: &str) -> Option<Cow<'a, str>> {
+        self.find(key).ok().map(|i| self.entries.remove(i).1)
+    }
+
+    /// Retain only the qualifiers for which the predicate returns `true`.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&str, &str) -> bool,
+    {
+        self.entries.retain(|(k, v)| f(k, v))
+    }
+
+    /// Remove all qualifiers.
+    pub fn clear(&mut self) {
+        self.entries.clear()
+    }
+
+    /// Iterate over the qualifiers in canonical order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&str, &str)> + ExactSizeIterator {
+        self.entries.iter().map(|(k, v)| (k.as_ref(), v.as_ref()))
+    }
+
+    /// Create qualifiers from lowercase keys already sorted without duplicates.
+    pub(crate) fn from_sorted(entries: &[(&'a str, &'a str)]) -> Self {
+        Self {
+            entries: entries
+                .iter()
+                .map(|&(k, v)| (Cow::Borrowed(k), Cow::Borrowed(v)))
+                .collect(),
+        }
+    }
+
+    /// Convert into qualifiers that own all of their keys and values.
+    pub fn into_owned(self) -> Qualifiers<'static> {
+        Qualifiers {
+            entries: self
+                .entries
+                .into_iter()
+                .map(|(k, v)| (Cow::Owned(k.into_owned()), Cow::Owned(v.into_owned())))
+                .collect(),
+        }
+    }
+
+    /// Get qualifiers borrowing all of their keys and values from `self`.
+    pub fn as_borrowed(&self) -> Qualifiers<'_> {
+        Qualifiers {
+            entries: self
+                .entries
+                .iter()
+                .map(|(k, v)| (Cow::Borrowed(k.as_ref()), Cow::Borrowed(v.as_ref())))
+                .collect(),
+        }
+    }
+
+    /// Locate a key using a case-insensitive binary search.
+    fn find(&self, key: &str) -> core::result::Result<usize, usize> {
+        self.entries
+            .binary_search_by(|(k, _)| cmp_ignore_case(k, key))
+    }
+}
+
+impl<'a> IntoIterator for Qualifiers<'a> {
+    type Item = (Cow<'a, str>, Cow<'a, str>);
+    type IntoIter = alloc::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Qualifiers<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Qualifiers<'_> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        use alloc::collections::BTreeMap;
+        use alloc::string::String;
+
+        let map = BTreeMap::<String, String>::deserialize(deserializer)?;
+        let mut qualifiers = Qualifiers::new();
+        for (k, v) in map {
+            qualifiers.insert(k, v).map_err(serde::de::Error::custom)?;
+        }
+        Ok(qualifiers)
+    }
+}
+
+/// Compare a lowercase key with another key, ignoring ASCII case.
+fn cmp_ignore_case(lower: &str, key: &str) -> Ordering {
+    lower
+        .bytes()
+        .cmp(key.bytes().map(|b| b.to_ascii_lowercase()))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_sorted_iteration() {
+        let mut qualifiers = Qualifiers::new();
+        qualifiers
+            .insert("repository_url", "repo.example.com")
+            .unwrap();
+        qualifiers.insert("arch", "x86_64").unwrap();
+        qualifiers.insert("Distro", "fedora-25").unwrap();
+        let keys = qualifiers.iter().map(|(k, _)| k).collect::<Vec<_>>();
+        assert_eq!(keys, ["arch", "distro", "repository_url"]);
+    }
+
+    #[test]
+    fn test_case_insensitive() {
+        let mut qualifiers = Qualifiers::new();
+        qualifiers.insert("Arch", "x86_64").unwrap();
+        assert_eq!(qualifiers.get("arch"), Some("x86_64"));
+        assert_eq!(qualifiers.get("ARCH"), Some("x86_64"));
+        let previous = qualifiers.insert("ARCH", "aarch64").unwrap();
+        assert_eq!(previous.as_deref(), Some("x86_64"));
+        assert_eq!(qualifiers.len(), 1);
+        assert_eq!(qualifiers.remove("aRcH").as_deref(), Some("aarch64"));
+        assert!(qualifiers.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_key() {
+        let mut qualifiers = Qualifiers::new();
+        let err = qualifiers.insert("1st-key", "value").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::InvalidKey("1st-key".into()));
+        assert!(qualifiers.is_empty());
+    }
+
+    #[test]
+    fn test_empty_value() {
+        let mut qualifiers = Qualifiers::new();
+        let err = qualifiers.insert("key", "").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::EmptyQualifierValue("key".into()));
+        assert!(qualifiers.is_empty());
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut qualifiers = Qualifiers::new();
+        qualifiers.insert("k1", "v1").unwrap();
+        qualifiers.insert("k2", "v2").unwrap();
+        qualifiers.retain(|k, _| k != "k2");
+        assert_eq!(qualifiers.iter().collect::<Vec<_>>(), [("k1", "v1")]);
+    }
+}
@@ -0,0 +1,336 @@
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+
+use super::ecosystems;
+use super::errors::Component;
+use super::errors::Error;
+use super::errors::ErrorKind;
+use super::errors::Result;
+use super::purl::PackageUrl;
+use super::rules::TypeRules;
+use super::utils;
+use super::validation;
+
+/// Whether a component of a Package URL must, may or must not be present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Requirement {
+    /// The component must be present.
+    Required,
+    /// The component may be present.
+    Optional,
+    /// The component must not be present.
+    Prohibited,
+}
+
+/// A normalization rule applied to a component, after lowercasing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Normalization {
+    /// Replace every occurrence of a character with another.
+    Replace(char, char),
+}
+
+/// The definition of a namespace, name or version for a type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ComponentDefinition {
+    requirement: Requirement,
+    case_sensitive: bool,
+    normalization: &'static [Normalization],
+}
+
+impl ComponentDefinition {
+    /// Get whether the component must, may or must not be present.
+    pub fn requirement(&self) -> Requirement {
+        self.requirement
+    }
+
+    /// Check whether the component is case-sensitive, or lowercased.
+    pub fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    /// Get the normalization rules applied after lowercasing.
+    pub fn normalization(&self) -> &'static [Normalization] {
+        self.normalization
+    }
+
+    /// Canonicalize a value of the component.
+    pub fn normalize<'a>(&self, value: Cow<'a, str>) -> Cow<'a, str> {
+        let mut value = if self.case_sensitive {
+            value
+        } else {
+            utils::to_lowercase(value)
+        };
+        for rule in self.normalization {
+            match *rule {
+                Normalization::Replace(from, to) => {
+                    if value.contains(from) {
+                        value = Cow::Owned(value.replace(from, to.encode_utf8(&mut [0; 4])));
+                    }
+                }
+            }
+        }
+        value
+    }
+}
+
+/// The definition of a qualifier known for a type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct QualifierDefinition {
+    key: &'static str,
+    requirement: Requirement,
+    description: &'static str,
+}
+
+impl QualifierDefinition {
+    /// Get the qualifier key.
+    pub fn key(&self) -> &'static str {
+        self.key
+    }
+
+    /// Get whether the qualifier must be present.
+    pub fn requirement(&self) -> Requirement {
+        self.requirement
+    }
+
+    /// Get the description of the qualifier.
+    pub fn description(&self) -> &'static str {
+        self.description
+    }
+}
+
+/// The definition of a Package URL type.
+///
+/// Definitions are generated from the `types/*-definition.json` files of
+/// the Package URL specification when building the crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TypeDefinition {
+    ty: &'static str,
+    type_name: &'static str,
+    description: &'static str,
+    default_repository_url: Option<&'static str>,
+    namespace: ComponentDefinition,
+    name: ComponentDefinition,
+    version: ComponentDefinition,
+    qualifiers: &'static [QualifierDefinition],
+}
+
+impl TypeDefinition {
+    /// Get the type, as it appears in a Package URL.
+    pub fn ty(&self) -> &'static str {
+        self.ty
+    }
+
+    /// Get the human-readable name of the type.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Get the description of the type.
+    pub fn description(&self) -> &'static str {
+        self.description
+    }
+
+    /// Get the `repository_url` implied when the qualifier is absent, if any.
+    pub fn default_repository_url(&self) -> Option<&'static str> {
+        self.default_repository_url
+    }
+
+    /// Get the definition of the namespace.
+    pub fn namespace(&self) -> &ComponentDefinition {
+        &self.namespace
+    }
+
+    /// Get the definition of the name.
+    pub fn name(&self) -> &ComponentDefinition {
+        &self.name
+    }
+
+    /// Get the definition of the version.
+    pub fn version(&self) -> &ComponentDefinition {
+        &self.version
+    }
+
+    /// Get the qualifiers known for the type.
+    pub fn qualifiers(&self) -> &'static [QualifierDefinition] {
+        self.qualifiers
+    }
+
+    /// Get the definition of the qualifier with the given key, if known.
+    pub fn qualifier(&self, key: &str) -> Option<&'static QualifierDefinition> {
+        self.qualifiers.iter().find(|q| q.key == key)
+    }
+}
+
+/// The type definitions and rules used to normalize and validate Package URLs.
+///
+/// The built-in registry contains the definitions of the types registered in
+/// the specification. A registry created with [`TypeRegistry::new`] starts
+/// with the same definitions, and custom [`TypeRules`] can be registered on
+/// top of them. Types with neither rules nor a definition follow the generic
+/// rules.
+///
+/// # Example
+/// ```rust
+/// # extern crate packageurl;
+/// use packageurl::{Requirement, TypeRegistry};
+///
+/// let maven = TypeRegistry::builtin().get("maven").unwrap();
+/// assert_eq!(maven.namespace().requirement(), Requirement::Required);
+/// assert_eq!(
+///     maven.default_repository_url(),
+///     Some("https://repo.maven.apache.org/maven2/")
+/// );
+/// ```
+pub struct TypeRegistry {
+    definitions: &'static [TypeDefinition],
+    rules: BTreeMap<String, Box<dyn TypeRules>>,
+}
+
+impl TypeRegistry {
+    /// Create a registry with the built-in definitions and no custom rules.
+    pub fn new() -> Self {
+        Self {
+            definitions: Self::builtin().definitions,
+            rules: BTreeMap::new(),
+        }
+    }
+
+    /// Get the registry of the types defined in the specification.
+    pub fn builtin() -> &'static Self {
+        static BUILTIN: TypeRegistry = TypeRegistry {
+            definitions: include!(concat!(env!("OUT_DIR"), "/definitions.rs")),
+            rules: BTreeMap::new(),
+        };
+        &BUILTIN
+    }
+
+    /// Register custom rules for a type, replacing its current rules.
+    ///
+    /// The type must be valid, otherwise an error will be returned.
+    pub fn register<T, R>(&mut self, ty: T, rules: R) -> Result<&mut Self>
+    where
+        T: Into<String>,
+        R: TypeRules + 'static,
+    {
+        let ty = ty.into();
+        if !validation::is_type_valid(&ty) {
+            return Err(Error::new(ErrorKind::InvalidType(ty)).in_component(Component::Type));
+        }
+        self.rules.insert(ty.to_lowercase(), Box::new(rules));
+        Ok(self)
+    }
+
+    /// Get the rules of the given lowercase type, if any.
+    ///
+    /// Custom rules take precedence over the built-in rules, which include
+    /// the type definition.
+    pub fn rules(&self, ty: &str) -> Option<&dyn TypeRules> {
+        match self.rules.get(ty) {
+            Some(rules) => Some(rules.as_ref()),
+            None => ecosystems::rules(ty).or_else(|| self.get(ty).map(|d| d as &dyn TypeRules)),
+        }
+    }
+
+    /// Check a Package URL against the rules of its type.
+    ///
+    /// The requirements of the type definition, such as a required namespace
+    /// or qualifier, are always checked, even when custom rules are
+    /// registered for the type.
+    pub fn validate(&self, purl: &PackageUrl) -> Result<()> {
+        self.check(purl, true)
+    }
+
+    /// Check a Package URL that may still be missing required components.
+    ///
+    /// This is used by the setters, since the missing components can be set
+    /// afterwards: a missing namespace or qualifier is not reported.
+    pub(crate) fn validate_incomplete(&self, purl: &PackageUrl) -> Result<()> {
+        self.check(purl, false)
+    }
+
+    /// Check a Package URL, reporting missing components if it is complete.
+    fn check(&self, purl: &PackageUrl, complete: bool) -> Result<()> {
+        let ty = purl.ty();
+        if let Some(definition) = self.get(ty) {
+            definition.check(purl, complete)?;
+        }
+        let result = match self.rules.get(ty) {
+            Some(rules) => rules.validate(purl),
+            None => ecosystems::rules(ty).map_or(Ok(()), |rules| rules.validate(purl)),
+        };
+        match result {
+            Err(e) if !complete => match e.kind() {
+                ErrorKind::MissingNamespace(_) | ErrorKind::MissingQualifier(_) => Ok(()),
+                _ => Err(e),
+            },
+            result => result,
+        }
+    }
+
+    /// Get the coordinates of a Package URL in its ecosystem, if known.
+    pub fn native_coordinates(&self, purl: &PackageUrl) -> Option<String> {
+        self.rules(purl.ty())?.native_coordinates(purl)
+    }
+
+    /// Get the definition of the given lowercase type, if any.
+    pub fn get(&self, ty: &str) -> Option<&TypeDefinition> {
+        // the position of the type in the built-in definitions, if any
+        let index: Option<usize> = include!(concat!(env!("OUT_DIR"), "/index.rs"));
+        index.map(|i| &self.definitions[i])
+    }
+
+    /// Iterate over the type definitions, sorted by type.
+    pub fn iter(&self) -> impl Iterator<Item = &TypeDefinition> {
+        self.definitions.iter()
+    }
+}
+
+impl Default for TypeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for TypeRegistry {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("TypeRegistry")
+            .field("definitions", &self.definitions.len())
+            .field("rules", &self.rules.keys())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn test_builtin() {
+        let registry = TypeRegistry::builtin();
+        let types = registry.iter().map(|d| d.ty()).collect::<Vec<_>>();
+        assert!(types.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(registry.get("npm").map(|d| d.ty()), Some("npm"));
+        for definition in registry.iter() {
+            let found = registry.get(definition.ty()).unwrap();
+            assert!(core::ptr::eq(found, definition));
+        }
+        assert!(registry.get("acme").is_none());
+    }
+
+    #[test]
+    fn test_normalize() {
+        let pypi = TypeRegistry::builtin().get("pypi").unwrap();
+        let name = pypi.name().normalize(Cow::Borrowed("Django_Rest"));
+        assert_eq!(name, "django-rest");
+        let namespace = pypi.namespace().normalize(Cow::Borrowed("Any"));
+        assert!(matches!(namespace, Cow::Borrowed("Any")));
+    }
+}
@@ -0,0 +1,140 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+use super::errors::Component;
+use super::errors::Error;
+use super::errors::ErrorKind;
+use super::errors::Result;
+use super::purl::PackageUrl;
+use super::registry::Requirement;
+use super::registry::TypeDefinition;
+
+/// Type-specific rules for normalizing and validating Package URLs.
+///
+/// Every method has a default implementation following the generic rules,
+/// so that only the relevant hooks need to be implemented. Rules are used
+/// once registered for a type in a [`TypeRegistry`], which can then be passed
+/// to [`PackageUrl::new_with`] or to [`PackageUrl::parse_with`] through the
+/// [`ParseOptions`].
+///
+/// The built-in rules of the types defined in the specification are given by
+/// their [`TypeDefinition`].
+///
+/// [`TypeRegistry`]: crate::TypeRegistry
+/// [`ParseOptions`]: crate::ParseOptions
+///
+/// # Example
+/// ```rust
+/// # extern crate packageurl;
+/// use std::borrow::Cow;
+/// use packageurl::{Error, ErrorKind, PackageUrl, ParseOptions, TypeRegistry, TypeRules};
+///
+/// struct InternalArtifact;
+///
+/// impl TypeRules for InternalArtifact {
+///     fn normalize_name<'a>(&self, name: Cow<'a, str>) -> Cow<'a, str> {
+///         Cow::Owned(name.to_uppercase())
+///     }
+///
+///     fn validate(&self, purl: &PackageUrl) -> Result<(), Error> {
+///         match purl.qualifiers().get("team") {
+///             Some(_) => Ok(()),
+///             None => Err(Error::new(ErrorKind::MissingQualifier("team".into()))),
+///         }
+///     }
+/// }
+///
+/// let mut registry = TypeRegistry::new();
+/// registry.register("internal-artifact", InternalArtifact).unwrap();
+/// let options = ParseOptions::new().registry(&registry);
+///
+/// let purl = PackageUrl::parse_with("pkg:internal-artifact/tool?team=ci", &options).unwrap();
+/// assert_eq!(purl.name(), "TOOL");
+/// assert!(PackageUrl::parse_with("pkg:internal-artifact/tool", &options).is_err());
+/// ```
+pub trait TypeRules: Send + Sync {
+    /// Canonicalize the namespace.
+    fn normalize_namespace<'a>(&self, namespace: Cow<'a, str>) -> Cow<'a, str> {
+        namespace
+    }
+
+    /// Canonicalize the name.
+    fn normalize_name<'a>(&self, name: Cow<'a, str>) -> Cow<'a, str> {
+        name
+    }
+
+    /// Canonicalize the version.
+    fn normalize_version<'a>(&self, version: Cow<'a, str>) -> Cow<'a, str> {
+        version
+    }
+
+    /// Canonicalize the components depending on each other, once all of them
+    /// are set, for instance a name whose case depends on a qualifier.
+    fn normalize(&self, purl: &mut PackageUrl<'_>) {
+        let _ = purl;
+    }
+
+    /// Check a parsed Package URL, for instance for required qualifiers.
+    fn validate(&self, purl: &PackageUrl) -> Result<()> {
+        let _ = purl;
+        Ok(())
+    }
+
+    /// Get the coordinates identifying the package in its ecosystem, if any.
+    fn native_coordinates(&self, purl: &PackageUrl) -> Option<String> {
+        let _ = purl;
+        None
+    }
+}
+
+impl TypeRules for TypeDefinition {
+    fn normalize_namespace<'a>(&self, namespace: Cow<'a, str>) -> Cow<'a, str> {
+        self.namespace().normalize(namespace)
+    }
+
+    fn normalize_name<'a>(&self, name: Cow<'a, str>) -> Cow<'a, str> {
+        self.name().normalize(name)
+    }
+
+    fn normalize_version<'a>(&self, version: Cow<'a, str>) -> Cow<'a, str> {
+        self.version().normalize(version)
+    }
+
+    fn validate(&self, purl: &PackageUrl) -> Result<()> {
+        self.check(purl, true)
+    }
+}
+
+impl TypeDefinition {
+    /// Check the requirements of the definition, skipping the missing
+    /// components unless the Package URL is complete.
+    pub(crate) fn check(&self, purl: &PackageUrl, complete: bool) -> Result<()> {
+        let namespace = match (self.namespace().requirement(), purl.namespace()) {
+            (Requirement::Required, None) if complete => {
+                Some(ErrorKind::MissingNamespace(self.ty().into()))
+            }
+            (Requirement::Prohibited, Some(_)) => {
+                Some(ErrorKind::ProhibitedNamespace(self.ty().into()))
+            }
+            _ => None,
+        };
+        if let Some(kind) = namespace {
+            return Err(Error::new(kind).in_component(Component::Namespace));
+        } else if !complete {
+            return Ok(());
+        }
+        let required = self
+            .qualifiers()
+            .iter()
+            .filter(|q| q.requirement() == Requirement::Required);
+        for qualifier in required {
+            if !purl.qualifiers().contains_key(qualifier.key()) {
+                return Err(
+                    Error::new(ErrorKind::MissingQualifier(qualifier.key().into()))
+                        .in_component(Component::Qualifier),
+                );
+            }
+        }
+        Ok(())
+    }
+}
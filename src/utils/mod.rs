@@ -1,8 +1,16 @@
+use alloc::borrow::Cow;
+
 mod encodable;
 mod quickfind;
+mod writers;
 
 pub use self::encodable::PercentCodec;
 pub use self::quickfind::QuickFind;
+pub use self::writers::CmpWriter;
+pub use self::writers::HashWriter;
+#[cfg(feature = "std")]
+pub use self::writers::IoWriter;
+pub use self::writers::LenWriter;
 
 pub fn rcut(input: &str, sep: u8) -> (&str, &str) {
     if let Some(i) = input.quickrfind(sep) {
@@ -20,6 +28,15 @@ pub fn cut(input: &str, sep: u8) -> (&str, &str) {
     }
 }
 
+/// Lowercase a string, only allocating if it contains uppercase characters.
+pub fn to_lowercase(input: Cow<'_, str>) -> Cow<'_, str> {
+    if input.chars().any(char::is_uppercase) {
+        Cow::Owned(input.to_lowercase())
+    } else {
+        input
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -38,4 +55,13 @@ mod tests {
         assert_eq!(rcut(buf, b':'), ("A:B", "C"));
         assert_eq!(rcut(buf, b','), ("", "A:B:C"));
     }
+
+    #[test]
+    fn test_to_lowercase() {
+        assert!(matches!(
+            to_lowercase(Cow::Borrowed("abc")),
+            Cow::Borrowed("abc")
+        ));
+        assert_eq!(to_lowercase(Cow::Borrowed("aBc")), "abc");
+    }
 }
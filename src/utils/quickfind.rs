@@ -1,6 +1,8 @@
 pub trait QuickFind {
     fn quickfind(&self, needle: u8) -> Option<usize>;
     fn quickrfind(&self, needle: u8) -> Option<usize>;
+    /// Iterate over the positions of any of three bytes, from the end.
+    fn quickrfind3(&self, n1: u8, n2: u8, n3: u8) -> impl Iterator<Item = usize> + '_;
 }
 
 #[cfg(not(feature = "memchr"))]
@@ -14,6 +16,12 @@ where
     fn quickrfind(&self, needle: u8) -> Option<usize> {
         self.as_ref().rfind(char::from(needle))
     }
+    fn quickrfind3(&self, n1: u8, n2: u8, n3: u8) -> impl Iterator<Item = usize> + '_ {
+        let bytes = self.as_ref().as_bytes();
+        (0..bytes.len())
+            .rev()
+            .filter(move |&i| bytes[i] == n1 || bytes[i] == n2 || bytes[i] == n3)
+    }
 }
 
 #[cfg(feature = "memchr")]
@@ -27,11 +35,16 @@ where
     fn quickrfind(&self, needle: u8) -> Option<usize> {
         ::memchr::memrchr(needle, self.as_ref().as_bytes())
     }
+    fn quickrfind3(&self, n1: u8, n2: u8, n3: u8) -> impl Iterator<Item = usize> + '_ {
+        ::memchr::memchr3_iter(n1, n2, n3, self.as_ref().as_bytes()).rev()
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
+    use alloc::vec::Vec;
+
     use super::*;
 
     #[test]
@@ -47,4 +60,12 @@ mod tests {
         assert_eq!(buf.quickrfind(b'o'), Some(8));
         assert_eq!(buf.quickrfind(b'c'), None)
     }
+
+    #[test]
+    fn test_quickrfind3() {
+        let buf = "Hello, world !";
+        let found = buf.quickrfind3(b'o', b'!', b'H').collect::<Vec<_>>();
+        assert_eq!(found, [13, 8, 4, 0]);
+        assert_eq!(buf.quickrfind3(b'a', b'b', b'c').next(), None);
+    }
 }
@@ -0,0 +1,202 @@
+use core::cmp::Ordering;
+use core::fmt::Result as FmtResult;
+use core::fmt::Write;
+use core::hash::Hasher;
+#[cfg(feature = "std")]
+use std::io;
+
+/// A `fmt::Write` sink feeding the written bytes into a hasher.
+///
+/// Bytes are buffered in fixed-size blocks so that the hash does not depend
+/// on how the output was split across `write_str` calls.
+pub struct HashWriter<'h, H: Hasher> {
+    hasher: &'h mut H,
+    buffer: [u8; 64],
+    len: usize,
+}
+
+impl<'h, H: Hasher> HashWriter<'h, H> {
+    pub fn new(hasher: &'h mut H) -> Self {
+        Self {
+            hasher,
+            buffer: [0; 64],
+            len: 0,
+        }
+    }
+
+    /// Flush the remaining bytes and terminate the hashed string.
+    pub fn finish(self) {
+        self.hasher.write(&self.buffer[..self.len]);
+        self.hasher.write_u8(0xff);
+    }
+}
+
+impl<H: Hasher> Write for HashWriter<'_, H> {
+    fn write_str(&mut self, s: &str) -> FmtResult {
+        let mut bytes = s.as_bytes();
+        while !bytes.is_empty() {
+            let n = bytes.len().min(self.buffer.len() - self.len);
+            self.buffer[self.len..self.len + n].copy_from_slice(&bytes[..n]);
+            self.len += n;
+            bytes = &bytes[n..];
+            if self.len == self.buffer.len() {
+                self.hasher.write(&self.buffer);
+                self.len = 0;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A `fmt::Write` sink comparing the written output against a string given
+/// in chunks.
+///
+/// Writing stops with an error as soon as the ordering is known.
+pub struct CmpWriter<'s, I> {
+    chunks: I,
+    rest: &'s str,
+    ordering: Ordering,
+}
+
+impl<'s, I: Iterator<Item = &'s str>> CmpWriter<'s, I> {
+    pub fn new(chunks: I) -> Self {
+        Self {
+            chunks,
+            rest: "",
+            ordering: Ordering::Equal,
+        }
+    }
+
+    /// Get the ordering of the written output relative to the string.
+    pub fn finish(mut self) -> Ordering {
+        match self.ordering {
+            Ordering::Equal if !self.rest.is_empty() => Ordering::Less,
+            Ordering::Equal if self.chunks.any(|c| !c.is_empty()) => Ordering::Less,
+            ordering => ordering,
+        }
+    }
+}
+
+impl<'s, I: Iterator<Item = &'s str>> Write for CmpWriter<'s, I> {
+    fn write_str(&mut self, s: &str) -> FmtResult {
+        let mut bytes = s.as_bytes();
+        while !bytes.is_empty() {
+            if self.rest.is_empty() {
+                match self.chunks.next() {
+                    Some(chunk) => self.rest = chunk,
+                    None => {
+                        self.ordering = Ordering::Greater;
+                        return Err(core::fmt::Error);
+                    }
+                }
+            }
+            let n = bytes.len().min(self.rest.len());
+            self.ordering = bytes[..n].cmp(&self.rest.as_bytes()[..n]);
+            if self.ordering != Ordering::Equal {
+                return Err(core::fmt::Error);
+            }
+            bytes = &bytes[n..];
+            self.rest = &self.rest[n..];
+        }
+        Ok(())
+    }
+}
+
+/// A `fmt::Write` sink counting the written bytes.
+pub struct LenWriter {
+    len: usize,
+}
+
+impl LenWriter {
+    pub fn new() -> Self {
+        Self { len: 0 }
+    }
+
+    /// Get the number of bytes written.
+    pub fn finish(self) -> usize {
+        self.len
+    }
+}
+
+impl Write for LenWriter {
+    fn write_str(&mut self, s: &str) -> FmtResult {
+        self.len += s.len();
+        Ok(())
+    }
+}
+
+/// A `fmt::Write` adapter over an `io::Write` stream.
+///
+/// Since `fmt::Error` carries no information, the I/O error that stopped
+/// writing is kept so that it can be returned to the caller.
+#[cfg(feature = "std")]
+pub struct IoWriter<'w, W: io::Write + ?Sized> {
+    inner: &'w mut W,
+    error: Option<io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<'w, W: io::Write + ?Sized> IoWriter<'w, W> {
+    pub fn new(inner: &'w mut W) -> Self {
+        Self { inner, error: None }
+    }
+
+    /// Get the I/O error that stopped writing.
+    pub fn into_error(self) -> io::Error {
+        self.error
+            .unwrap_or_else(|| io::Error::other("formatter error"))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write + ?Sized> Write for IoWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> FmtResult {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            core::fmt::Error
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::hash_map::DefaultHasher;
+
+    use super::*;
+
+    #[test]
+    fn test_hash_writer_chunks() {
+        let mut h1 = DefaultHasher::new();
+        let mut w1 = HashWriter::new(&mut h1);
+        w1.write_str(&"ab".repeat(50)).unwrap();
+        w1.finish();
+
+        let mut h2 = DefaultHasher::new();
+        let mut w2 = HashWriter::new(&mut h2);
+        for _ in 0..100 {
+            w2.write_str(if w2.len % 2 == 0 { "a" } else { "b" })
+                .unwrap();
+        }
+        w2.finish();
+
+        assert_eq!(h1.finish(), h2.finish());
+    }
+
+    #[test]
+    fn test_cmp_writer() {
+        let cmp = |chunks: &[&str], other: &[&str]| {
+            let mut w = CmpWriter::new(other.iter().copied());
+            let _ = chunks.iter().try_for_each(|c| w.write_str(c));
+            w.finish()
+        };
+        assert_eq!(cmp(&["ab", "c"], &["abc"]), Ordering::Equal);
+        assert_eq!(cmp(&["ab", "c"], &["a", "", "bc"]), Ordering::Equal);
+        assert_eq!(cmp(&["ab"], &["abc"]), Ordering::Less);
+        assert_eq!(cmp(&["ab"], &["ab", "", "c"]), Ordering::Less);
+        assert_eq!(cmp(&["ab"], &["ab", ""]), Ordering::Equal);
+        assert_eq!(cmp(&["ab", "cd"], &["a", "bc"]), Ordering::Greater);
+        assert_eq!(cmp(&["ab", "b"], &["abc"]), Ordering::Less);
+        assert_eq!(cmp(&["b"], &["abc"]), Ordering::Greater);
+    }
+}
@@ -1,46 +1,92 @@
 /// Check whether a type is valid regarding the specification.
 pub fn is_type_valid(ty: &str) -> bool {
+    type_violation(ty).is_none()
+}
+
+/// Explain which rule of the specification an invalid type breaks.
+pub fn type_violation(ty: &str) -> Option<&'static str> {
     let first = match ty.chars().next() {
         Some(c) => c,
-        None => return false,
+        None => return Some("the type must not be empty"),
     };
     if first.is_ascii_digit() {
-        return false;
+        return Some("the type must not start with a digit");
     }
 
     #[allow(clippy::match_like_matches_macro)]
-    ty.chars().all(|c| match c {
+    let valid = ty.chars().all(|c| match c {
         '.' | '-' | '+' | 'a'..='z' | 'A'..='Z' | '0'..='9' => true,
         _ => false,
-    })
+    });
+    if valid {
+        None
+    } else {
+        Some("the type must only contain ASCII letters, digits, '.', '+' and '-'")
+    }
 }
 
 /// Check whether a qualifier key is valid regarding the specification.
 pub fn is_qualifier_key_valid(key: &str) -> bool {
+    qualifier_key_violation(key).is_none()
+}
+
+/// Explain which rule of the specification an invalid qualifier key breaks.
+pub fn qualifier_key_violation(key: &str) -> Option<&'static str> {
     // check the key doesn't start with a digit
     let first = match key.chars().next() {
         Some(c) => c,
-        None => return false,
+        None => return Some("qualifier keys must not be empty"),
     };
     if first.is_ascii_digit() {
-        return false;
+        return Some("qualifier keys must not start with a digit");
     }
 
     // check the key contains only valid characters
     // The key must be composed only of ASCII letters and numbers, '.', '-' and '_' (period, dash and underscore)
     #[allow(clippy::match_like_matches_macro)]
-    key.chars().all(|c| match c {
+    let valid = key.chars().all(|c| match c {
         '.' | '-' | '_' | 'a'..='z' | 'A'..='Z' | '0'..='9' => true,
         _ => false,
-    })
+    });
+    if valid {
+        None
+    } else {
+        Some("qualifier keys must only contain ASCII letters, digits, '.', '-' and '_'")
+    }
 }
 
 /// Check whether a namespace component is valid regarding the specification.
 pub fn is_namespace_component_valid(component: &str) -> bool {
-    !component.is_empty() && !component.chars().any(|c| c == '/')
+    namespace_component_violation(component).is_none()
+}
+
+/// Explain which rule of the specification an invalid namespace component breaks.
+pub fn namespace_component_violation(component: &str) -> Option<&'static str> {
+    if component.is_empty() {
+        Some("namespace segments must not be empty")
+    } else if component == "." || component == ".." {
+        Some("namespace segments must not be '.' or '..'")
+    } else if component.chars().any(|c| c == '/') {
+        Some("namespace segments must not contain a '/' once decoded")
+    } else {
+        None
+    }
 }
 
 /// Check whether a subpath segment is valid regarding the specification.
 pub fn is_subpath_segment_valid(segment: &str) -> bool {
-    !segment.is_empty() && segment != "." && segment != ".." && !segment.chars().any(|c| c == '/')
+    subpath_segment_violation(segment).is_none()
+}
+
+/// Explain which rule of the specification an invalid subpath segment breaks.
+pub fn subpath_segment_violation(segment: &str) -> Option<&'static str> {
+    if segment.is_empty() {
+        Some("subpath segments must not be empty")
+    } else if segment == "." || segment == ".." {
+        Some("subpath segments must not be '.' or '..'")
+    } else if segment.chars().any(|c| c == '/') {
+        Some("subpath segments must not contain a '/' once decoded")
+    } else {
+        None
+    }
 }
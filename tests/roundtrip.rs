@@ -0,0 +1,157 @@
+//! Property tests checking that every Package URL that can be constructed
+//! is parsed back from its canonical string without loss.
+
+use std::str::FromStr;
+
+use packageurl::PackageUrl;
+use packageurl::TypeRegistry;
+use proptest::collection::vec;
+use proptest::option;
+use proptest::prelude::*;
+
+/// A type, either with type-specific rules or arbitrary.
+fn ty() -> impl Strategy<Value = String> {
+    prop_oneof![
+        prop::sample::select(vec![
+            "github",
+            "npm",
+            "pypi",
+            "rpm",
+            "huggingface",
+            "deb",
+            "oci",
+            "cpan",
+            "mlflow",
+        ])
+        .prop_map(String::from),
+        "[a-zA-Z][a-zA-Z0-9.+-]{0,8}",
+    ]
+}
+
+/// A namespace or subpath segment, which cannot contain a '/'.
+fn segment() -> impl Strategy<Value = String> {
+    "[^/]{1,8}".prop_filter("local and parent segments are invalid", |s| {
+        s != "." && s != ".."
+    })
+}
+
+/// Any string, including delimiters and percent signs.
+fn text(min: usize) -> impl Strategy<Value = String> {
+    proptest::string::string_regex(&format!("(?s).{{{},12}}", min)).unwrap()
+}
+
+/// A name, either arbitrary or shaped like a Perl module or distribution.
+fn name() -> impl Strategy<Value = String> {
+    prop_oneof![text(1), "[A-Z][a-z]{1,4}(::|-)[A-Z][a-z]{1,4}"]
+}
+
+/// A version, either arbitrary or an image digest.
+fn version() -> impl Strategy<Value = String> {
+    prop_oneof![text(0), "sha256:[0-9a-f]{64}"]
+}
+
+/// A qualifier, either arbitrary or the URL of a Databricks workspace.
+fn qualifier() -> impl Strategy<Value = (String, String)> {
+    prop_oneof![
+        ("[a-zA-Z][a-zA-Z0-9._-]{0,6}", text(1)),
+        Just((
+            "repository_url".into(),
+            "https://adb-123.4.azuredatabricks.net".into()
+        )),
+    ]
+}
+
+/// The components of a Package URL, in the order they are set.
+type Components = (
+    String,
+    Option<Vec<String>>,
+    String,
+    Option<String>,
+    Vec<(String, String)>,
+    Option<Vec<String>>,
+);
+
+/// Build a Package URL from its components, or `None` if they are invalid.
+fn build(
+    (ty, namespace, name, version, qualifiers, subpath): Components,
+) -> Option<PackageUrl<'static>> {
+    let mut builder = PackageUrl::builder(ty, name);
+    if let Some(ns) = namespace {
+        builder = builder.namespace(ns.join("/"));
+    }
+    if let Some(v) = version {
+        builder = builder.version(v);
+    }
+    for (k, v) in qualifiers {
+        builder = builder.qualifier(k, v);
+    }
+    if let Some(sp) = subpath {
+        builder = builder.subpath(sp.join("/"));
+    }
+    builder.build().ok()
+}
+
+/// Set the components of a Package URL one at a time, or return `None` if
+/// any of them is invalid.
+fn set(
+    (ty, namespace, name, version, qualifiers, subpath): Components,
+) -> Option<PackageUrl<'static>> {
+    let mut purl = PackageUrl::new(ty, "placeholder").ok()?;
+    if let Some(ns) = namespace {
+        purl.with_namespace(ns.join("/")).ok()?;
+    }
+    purl.with_name(name).ok()?;
+    if let Some(v) = version {
+        purl.with_version(v).ok()?;
+    }
+    for (k, v) in qualifiers {
+        purl.add_qualifier(k, v).ok()?;
+    }
+    if let Some(sp) = subpath {
+        purl.with_subpath(sp.join("/")).ok()?;
+    }
+    TypeRegistry::builtin().validate(&purl).ok()?;
+    Some(purl)
+}
+
+/// A Package URL following the rules of its type, built either at once or
+/// with the setters.
+fn purl() -> impl Strategy<Value = PackageUrl<'static>> {
+    (
+        any::<bool>(),
+        (
+            ty(),
+            option::of(vec(segment(), 1..4)),
+            name(),
+            option::of(version()),
+            vec(qualifier(), 0..4),
+            option::of(vec(segment(), 1..4)),
+        ),
+    )
+        .prop_filter_map(
+            "the type rules reject the Package URL",
+            |(with_setters, components)| {
+                if with_setters {
+                    set(components)
+                } else {
+                    build(components)
+                }
+            },
+        )
+}
+
+proptest! {
+    #[test]
+    fn canonical_roundtrip(purl in purl()) {
+        let canonical = purl.to_string();
+        let parsed = PackageUrl::from_str(&canonical).unwrap();
+        prop_assert_eq!(&parsed, &purl);
+        prop_assert_eq!(parsed.ty(), purl.ty());
+        prop_assert_eq!(parsed.namespace(), purl.namespace());
+        prop_assert_eq!(parsed.name(), purl.name());
+        prop_assert_eq!(parsed.version(), purl.version());
+        prop_assert_eq!(parsed.qualifiers(), purl.qualifiers());
+        prop_assert_eq!(parsed.subpath(), purl.subpath());
+        prop_assert_eq!(parsed.to_string(), canonical);
+    }
+}
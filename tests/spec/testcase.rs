@@ -1,35 +1,134 @@
-use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::path::Path;
+use std::str::FromStr;
 
-// May 20, 2019
-static TEST_SUITE: &[u8] = include_bytes!("test-suite-data.json");
+use packageurl::PackageUrl;
+use serde_json::Value;
+
+/// A file of the specification test suite.
+#[derive(Deserialize)]
+pub struct TestSuite {
+    pub tests: Vec<TestCase>,
+}
+
+impl TestSuite {
+    pub fn load(path: &Path) -> Self {
+        let data = std::fs::read(path).unwrap();
+        serde_json::from_slice(&data).unwrap()
+    }
+}
+
+/// The operation checked by a test case.
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum TestType {
+    /// Parse a string into its components.
+    Parse,
+    /// Build a canonical string from components.
+    Build,
+    /// Parse a string and format it in canonical form.
+    Roundtrip,
+}
+
+impl Display for TestType {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(match self {
+            TestType::Parse => "parse",
+            TestType::Build => "build",
+            TestType::Roundtrip => "roundtrip",
+        })
+    }
+}
 
 #[derive(Deserialize)]
 #[allow(dead_code)]
-pub struct SpecTestCase<'a> {
-    pub description: Cow<'a, str>,
-    pub purl: Cow<'a, str>,
-    pub canonical_purl: Option<Cow<'a, str>>,
+pub struct TestCase {
+    pub description: String,
+    pub test_group: String,
+    pub test_type: TestType,
+    pub input: Value,
+    pub expected_output: Value,
+    pub expected_failure: bool,
+    pub expected_failure_reason: Option<String>,
+}
+
+/// The components of a Package URL, as found in the test suite.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct Components {
     #[serde(rename = "type")]
-    pub ty: Option<Cow<'a, str>>,
-    pub namespace: Option<Cow<'a, str>>,
-    pub name: Option<Cow<'a, str>>,
-    pub version: Option<Cow<'a, str>>,
-    pub qualifiers: Option<HashMap<Cow<'a, str>, Cow<'a, str>>>,
-    pub subpath: Option<Cow<'a, str>>,
-    pub is_invalid: bool,
-}
-
-impl<'a> SpecTestCase<'a> {
-    pub fn new(desc: &'a str) -> Self {
-        if let Ok(::serde_json::Value::Array(v)) = ::serde_json::from_slice(TEST_SUITE) {
-            let json = v
-                .into_iter()
-                .find(|x| x["description"].as_str().unwrap().eq(desc))
-                .unwrap();
-            ::serde_json::from_value(json).unwrap()
-        } else {
-            unreachable!("invalid json file")
+    pub ty: Option<String>,
+    pub namespace: Option<String>,
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub qualifiers: Option<BTreeMap<String, String>>,
+    pub subpath: Option<String>,
+}
+
+impl From<&PackageUrl<'_>> for Components {
+    fn from(purl: &PackageUrl) -> Self {
+        let qualifiers = purl
+            .qualifiers()
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<BTreeMap<_, _>>();
+        Self {
+            ty: Some(purl.ty().to_string()),
+            namespace: purl.namespace().map(String::from),
+            name: Some(purl.name().to_string()),
+            version: purl.version().map(String::from),
+            qualifiers: Some(qualifiers).filter(|q| !q.is_empty()),
+            subpath: purl.subpath().map(String::from),
+        }
+    }
+}
+
+impl TestCase {
+    /// Run the test case, describing the mismatch if it fails.
+    pub fn run(&self) -> Result<(), String> {
+        let result = match self.test_type {
+            TestType::Parse => self.parse().map(|c| serde_json::to_value(c).unwrap()),
+            TestType::Build => self.build().map(Value::String),
+            TestType::Roundtrip => self.roundtrip().map(Value::String),
+        };
+        match result {
+            Ok(_) if self.expected_failure => Err("expected a failure".into()),
+            Err(_) if self.expected_failure => Ok(()),
+            Err(e) => Err(format!("unexpected error: {}", e)),
+            Ok(output) if output == self.expected_output => Ok(()),
+            Ok(output) => Err(format!("expected {}, got {}", self.expected_output, output)),
+        }
+    }
+
+    fn input_str(&self) -> &str {
+        self.input.as_str().expect("input should be a string")
+    }
+
+    fn parse(&self) -> packageurl::Result<Components> {
+        PackageUrl::from_str(self.input_str()).map(|purl| Components::from(&purl))
+    }
+
+    fn roundtrip(&self) -> packageurl::Result<String> {
+        PackageUrl::from_str(self.input_str()).map(|purl| purl.to_string())
+    }
+
+    fn build(&self) -> packageurl::Result<String> {
+        let c: Components = serde_json::from_value(self.input.clone()).unwrap();
+        let mut builder = PackageUrl::builder(c.ty.unwrap_or_default(), c.name.unwrap_or_default());
+        if let Some(ns) = c.namespace {
+            builder = builder.namespace(ns);
+        }
+        if let Some(v) = c.version {
+            builder = builder.version(v);
+        }
+        for (k, v) in c.qualifiers.unwrap_or_default() {
+            builder = builder.qualifier(k, v);
+        }
+        if let Some(sp) = c.subpath {
+            builder = builder.subpath(sp);
         }
+        builder.build().map(|purl| purl.to_string())
     }
 }